@@ -0,0 +1,26 @@
+use std::{hint::black_box, rc::Rc};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use subrc::Subrc;
+
+struct Record {
+    _id: u64,
+    items: [u32; 16],
+}
+
+fn deref(c: &mut Criterion) {
+    let rc = Rc::new(Record {
+        _id: 1,
+        items: [1; 16],
+    });
+    let items = Subrc::new(rc.clone(), |record| &record.items);
+
+    c.bench_function("rc field", |b| b.iter(|| black_box(&rc).items[3]));
+    c.bench_function("subrc deref", |b| b.iter(|| black_box(&items)[3]));
+    c.bench_function("subrc new", |b| {
+        b.iter(|| Subrc::new(black_box(&rc).clone(), |record| &record.items[3]))
+    });
+}
+
+criterion_group!(benches, deref);
+criterion_main!(benches);
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use arc_swap::{ArcSwap, Guard};
+
+use crate::{Pointee, Subarc};
+
+fn unwrap_or_clone<T, U: ?Sized + Pointee>(arc: Arc<Subarc<T, U>>) -> Subarc<T, U> {
+    Arc::try_unwrap(arc).unwrap_or_else(|arc| (*arc).clone())
+}
+
+/**
+* A slot holding a [`Subarc`], which can be loaded and replaced atomically from any thread, like
+* an [`ArcSwap`].
+*
+* Loading never blocks, so readers can keep using the current projection while writers replace
+* it, e.g. to hot-reload a section of some configuration.
+*
+* # Example
+```rust
+# use std::sync::Arc;
+# use subrc::{subarc, AtomicSubarc};
+struct Config {
+    name: String,
+}
+
+let config = Arc::new(Config { name: String::from("old") });
+let slot = AtomicSubarc::new(subarc!(config.name));
+assert_eq!(slot.load().as_str(), "old");
+
+let config = Arc::new(Config { name: String::from("new") });
+let old = slot.swap(subarc!(config.name));
+assert_eq!(*old, "old");
+assert_eq!(slot.load().as_str(), "new");
+```
+*/
+pub struct AtomicSubarc<T, U: ?Sized + Pointee> {
+    slot: ArcSwap<Subarc<T, U>>,
+}
+
+impl<T, U> AtomicSubarc<T, U>
+where
+    U: ?Sized + Pointee,
+{
+    /// Create a slot holding `subarc`.
+    pub fn new(subarc: Subarc<T, U>) -> Self {
+        AtomicSubarc {
+            slot: ArcSwap::from_pointee(subarc),
+        }
+    }
+
+    /**
+       Load the current projection, without blocking. The returned guard derefs to it, and is
+       meant to be short-lived, see [`ArcSwap::load`].
+    */
+    pub fn load(&self) -> Guard<Arc<Subarc<T, U>>> {
+        self.slot.load()
+    }
+
+    /// Load a clone of the current projection, see [`ArcSwap::load_full`].
+    pub fn load_full(&self) -> Subarc<T, U> {
+        (**self.slot.load()).clone()
+    }
+
+    /// Replace the current projection with `subarc`.
+    pub fn store(&self, subarc: Subarc<T, U>) {
+        self.slot.store(Arc::new(subarc));
+    }
+
+    /// Replace the current projection with `subarc`, and return the previous one.
+    pub fn swap(&self, subarc: Subarc<T, U>) -> Subarc<T, U> {
+        unwrap_or_clone(self.slot.swap(Arc::new(subarc)))
+    }
+
+    /**
+       Replace the current projection with the one returned by `f` given the current one, and
+       return the previous one, see [`ArcSwap::rcu`].
+
+       If the projection is replaced concurrently, `f` is called again with the new one, so it may
+       be called several times.
+    */
+    pub fn rcu<F>(&self, mut f: F) -> Subarc<T, U>
+    where
+        F: FnMut(&Subarc<T, U>) -> Subarc<T, U>,
+    {
+        unwrap_or_clone(self.slot.rcu(|current| f(current)))
+    }
+
+    /// Consume the slot, returning the current projection.
+    pub fn into_inner(self) -> Subarc<T, U> {
+        unwrap_or_clone(self.slot.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{AtomicSubarc, Subarc};
+
+    struct Config {
+        sections: [String; 2],
+    }
+
+    fn config(name: &str) -> Arc<Config> {
+        Arc::new(Config {
+            sections: [format!("{}.0", name), format!("{}.1", name)],
+        })
+    }
+
+    #[test]
+    fn test_load_store() {
+        let first = config("first");
+        let slot = AtomicSubarc::new(Subarc::new(first.clone(), |c| &c.sections[0]));
+        assert!(std::ptr::eq(
+            slot.load().as_str(),
+            first.sections[0].as_str()
+        ));
+
+        let second = config("second");
+        slot.store(Subarc::new(second.clone(), |c| &c.sections[1]));
+        assert_eq!(Arc::strong_count(&first), 1);
+        assert_eq!(*slot.load_full(), "second.1");
+        assert_eq!(*slot.into_inner(), "second.1");
+    }
+
+    #[test]
+    fn test_rcu_across_threads() {
+        let slot = Arc::new(AtomicSubarc::new(Subarc::new(config("0"), |c| {
+            &c.sections[0]
+        })));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let slot = slot.clone();
+                std::thread::spawn(move || {
+                    slot.rcu(|current| {
+                        let next = current.parse::<f32>().unwrap() + 1.0;
+                        Subarc::new(config(&next.to_string()), |c| &c.sections[0])
+                    })
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*slot.load_full(), "4.0");
+    }
+}
@@ -0,0 +1,143 @@
+use core::ops::{Bound, RangeBounds};
+
+use crate::{SharedOwner, Subptr};
+#[cfg(feature = "bytes")]
+use crate::SubarcBytes;
+
+impl<P> Subptr<P, [u8]>
+where
+    P: SharedOwner,
+    P::Target: AsRef<[u8]>,
+{
+    /**
+       Create a [`Subptr`] to the bytes in `range` of the owned buffer, or [`None`] if the range
+       is out of its bounds.
+
+       The buffer may be anything which can be viewed as bytes, such as a `Vec<u8>`, a `[u8]`, or
+       a memory map like `memmap2::Mmap`: an `Rc<Mmap>` lets many parsers hold zero-copy
+       [`SubrcBytes`](crate::SubrcBytes) views into a mapped file, which stays mapped as long as
+       any of them lives. Like with [`new_indirect`](Subptr::new_indirect), the bytes may lie
+       behind an indirection.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::SubrcBytes;
+           let rc = Rc::new(b"GIF89a\x01\x00\x01\x00".to_vec());
+           let magic: SubrcBytes<Vec<u8>> = SubrcBytes::project_range(&rc, ..6).unwrap();
+           let width = SubrcBytes::project_range(&rc, 6..8).unwrap();
+           assert_eq!(&*magic, b"GIF89a");
+           assert_eq!(u16::from_le_bytes([width[0], width[1]]), 1);
+           assert!(SubrcBytes::project_range(&rc, 8..12).is_none());
+       ```
+    */
+    pub fn project_range<R>(owner: &P, range: R) -> Option<Self>
+    where
+        R: RangeBounds<usize>,
+    {
+        let bounds: (Bound<usize>, Bound<usize>) =
+            (range.start_bound().cloned(), range.end_bound().cloned());
+        let ptr = (**owner).as_ref().get(bounds)? as *const [u8];
+        // SAFETY: the bytes were borrowed from the owned value, which the clone of `owner` keeps
+        // alive and shared, see `new_indirect`.
+        Some(unsafe { Subptr::from_raw_parts(owner.clone(), ptr) })
+    }
+}
+
+/**
+ Convert a projection into a thread-safe buffer into a [`Bytes`](::bytes::Bytes) without copying,
+ with [`Bytes::from_owner`](::bytes::Bytes::from_owner).
+
+ ## Example
+ ```rust
+     # use std::sync::Arc;
+     # use subrc::SubarcBytes;
+     use bytes::Bytes;
+
+     let frame = Arc::new(b"\x00\x05hello".to_vec());
+     let payload: Bytes = SubarcBytes::project_range(&frame, 2..).unwrap().into();
+     assert_eq!(payload, "hello");
+     assert!(std::ptr::eq(payload.as_ptr(), &frame[2]));
+ ```
+*/
+#[cfg(feature = "bytes")]
+impl<T> From<SubarcBytes<T>> for ::bytes::Bytes
+where
+    T: ?Sized + Send + Sync + 'static,
+{
+    fn from(subarc: SubarcBytes<T>) -> Self {
+        ::bytes::Bytes::from_owner(subarc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use crate::{SubarcBytes, SubrcBytes};
+
+    #[test]
+    fn test_project_range() {
+        let rc: Rc<[u8]> = Rc::from(&b"header:payload"[..]);
+        let header = SubrcBytes::project_range(&rc, ..6).unwrap();
+        let payload = SubrcBytes::project_range(&rc, 7..).unwrap();
+        assert!(std::ptr::eq(&*header, &rc[..6]));
+        assert!(std::ptr::eq(&*payload, &rc[7..]));
+        assert!(SubrcBytes::project_range(&rc, 7..=14).is_none());
+        assert_eq!(Rc::strong_count(&rc), 3);
+    }
+
+    #[test]
+    fn test_project_range_shared() {
+        let arc = Arc::new(vec![1u8, 2, 3, 4]);
+        let views: Vec<SubarcBytes<Vec<u8>>> = (0..4)
+            .map(|start| SubarcBytes::project_range(&arc, start..).unwrap())
+            .collect();
+        drop(arc);
+        std::thread::spawn(move || assert_eq!(&*views[1], [2, 3, 4]))
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn test_into_bytes() {
+        use ::bytes::Bytes;
+
+        let arc: Arc<[u8]> = Arc::from(&b"GET / HTTP/1.1"[..]);
+        let path = Bytes::from(SubarcBytes::project_range(&arc, 4..5).unwrap());
+        assert_eq!(path, "/");
+        assert!(std::ptr::eq(path.as_ptr(), &arc[4]));
+
+        let version = Bytes::from(SubarcBytes::project_range(&arc, 6..).unwrap()).slice(5..);
+        drop(path);
+        assert_eq!(Arc::strong_count(&arc), 2);
+        drop(arc);
+        assert_eq!(version, "1.1");
+    }
+
+    #[test]
+    #[cfg(feature = "memmap2")]
+    fn test_project_range_of_mmap() {
+        use std::{fs, fs::File};
+
+        use ::memmap2::Mmap;
+
+        let path = std::env::temp_dir().join(format!("subrc-mmap-{}", std::process::id()));
+        fs::write(&path, b"GIF89a\x01\x00\x02\x00").unwrap();
+        // SAFETY: the file is private to this test, and isn't modified while it is mapped.
+        let mmap = unsafe { Mmap::map(&File::open(&path).unwrap()) }.unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let rc = Rc::new(mmap);
+        let magic: SubrcBytes<Mmap> = SubrcBytes::project_range(&rc, ..6).unwrap();
+        let size = SubrcBytes::project_range(&rc, 6..).unwrap();
+        assert!(std::ptr::eq(&*magic, &rc[..6]));
+        assert!(SubrcBytes::project_range(&rc, 6..12).is_none());
+
+        drop(rc);
+        assert_eq!(Rc::strong_count(magic.owner()), 2);
+        assert_eq!(&*magic, b"GIF89a");
+        assert_eq!(&*size, [1, 0, 2, 0]);
+    }
+}
@@ -0,0 +1,462 @@
+use alloc::{boxed::Box, rc::Rc};
+use core::{
+    cell::{BorrowError, BorrowMutError, Cell, Ref, RefCell, RefMut},
+    mem,
+    ops::{Deref, DerefMut},
+};
+
+use crate::subptr::{get_offset, invalid_projection};
+
+/**
+* A shared borrow of a subregion of the value in an `Rc<RefCell<T>>`, which also keeps the
+* [`RefCell`] alive.
+*
+* Like a [`Ref`] mapped to the subregion, the [`RefCell`] stays borrowed until the [`SubRef`] is
+* dropped, but there is no lifetime tying it to the [`Rc`], so it can be handed out freely.
+*
+* # Example
+```rust
+# use std::{cell::RefCell, rc::Rc};
+# use subrc::SubRef;
+struct State {
+    name: String,
+    count: u32,
+}
+
+let rc = Rc::new(RefCell::new(State { name: String::from("state"), count: 0 }));
+let name = SubRef::new(rc.clone(), |state| &state.name);
+assert_eq!(*name, "state");
+assert!(rc.try_borrow_mut().is_err());
+
+drop(name);
+rc.borrow_mut().count += 1;
+```
+*/
+pub struct SubRef<T: ?Sized, U: ?Sized> {
+    ptr: *const U,
+    // Declared before the owner so that the borrow is released before the `RefCell` is dropped.
+    _guard: Ref<'static, ()>,
+    _owner: Rc<RefCell<T>>,
+}
+
+impl<T, U> SubRef<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /**
+       Immutably borrow the value in `owner`, and project the borrow to the subregion returned by
+       `getter`.
+
+       # Panics
+       Panics if the value is currently mutably borrowed, see [`RefCell::borrow`]. Use
+       [`try_new`](SubRef::try_new) to handle this case gracefully.
+    */
+    #[track_caller]
+    pub fn new<F>(owner: Rc<RefCell<T>>, getter: F) -> Self
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        match Self::try_new(owner, getter) {
+            Ok(sub) => sub,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /**
+       Like [`new`](SubRef::new), but returns a [`BorrowError`] instead of panicking if the value
+       is currently mutably borrowed.
+    */
+    pub fn try_new<F>(owner: Rc<RefCell<T>>, getter: F) -> Result<Self, BorrowError>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let mut ptr = None;
+        let guard = Ref::map(owner.try_borrow()?, |t| {
+            ptr = Some(getter(t) as *const U);
+            &()
+        });
+        // SAFETY: the guard only refers to the `RefCell`, which is kept alive by `_owner` until
+        // after the guard is dropped.
+        let guard = unsafe { mem::transmute::<Ref<'_, ()>, Ref<'static, ()>>(guard) };
+        Ok(SubRef {
+            ptr: ptr.expect("`Ref::map` calls the getter"),
+            _guard: guard,
+            _owner: owner,
+        })
+    }
+
+    /**
+       Project the borrow further down, to a subregion of its current target, like [`Ref::map`].
+
+       This is an associated function, so it doesn't shadow a method of the target.
+    */
+    pub fn map<V, F>(this: Self, f: F) -> SubRef<T, V>
+    where
+        V: ?Sized,
+        F: FnOnce(&U) -> &V,
+    {
+        let ptr = f(&*this) as *const V;
+        SubRef {
+            ptr,
+            _guard: this._guard,
+            _owner: this._owner,
+        }
+    }
+}
+
+impl<T, U> Deref for SubRef<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: the target is kept borrowed and alive by the guard and the owner.
+        unsafe { &*self.ptr }
+    }
+}
+
+/**
+* A mutable borrow of a subregion of the value in an `Rc<RefCell<T>>`, which also keeps the
+* [`RefCell`] alive.
+*
+* Like a [`RefMut`] mapped to the subregion, the [`RefCell`] stays borrowed until the
+* [`SubRefMut`] is dropped.
+*
+* # Example
+```rust
+# use std::{cell::RefCell, rc::Rc};
+# use subrc::SubRefMut;
+struct State {
+    name: String,
+    count: u32,
+}
+
+let rc = Rc::new(RefCell::new(State { name: String::from("state"), count: 0 }));
+let mut count = SubRefMut::new(rc.clone(), |state| &mut state.count);
+*count += 1;
+drop(count);
+assert_eq!(rc.borrow().count, 1);
+```
+*/
+pub struct SubRefMut<T: ?Sized, U: ?Sized> {
+    ptr: *mut U,
+    // Declared before the owner so that the borrow is released before the `RefCell` is dropped.
+    _guard: RefMut<'static, ()>,
+    _owner: Rc<RefCell<T>>,
+}
+
+impl<T, U> SubRefMut<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /**
+       Mutably borrow the value in `owner`, and project the borrow to the subregion returned by
+       `getter`.
+
+       # Panics
+       Panics if the value is currently borrowed, see [`RefCell::borrow_mut`]. Use
+       [`try_new`](SubRefMut::try_new) to handle this case gracefully.
+    */
+    #[track_caller]
+    pub fn new<F>(owner: Rc<RefCell<T>>, getter: F) -> Self
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        match Self::try_new(owner, getter) {
+            Ok(sub) => sub,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /**
+       Like [`new`](SubRefMut::new), but returns a [`BorrowMutError`] instead of panicking if the
+       value is currently borrowed.
+    */
+    pub fn try_new<F>(owner: Rc<RefCell<T>>, getter: F) -> Result<Self, BorrowMutError>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let mut ptr = None;
+        let guard = RefMut::map(owner.try_borrow_mut()?, |t| {
+            ptr = Some(getter(t) as *mut U);
+            Box::leak(Box::new(()))
+        });
+        // SAFETY: see `SubRef::try_new`.
+        let guard = unsafe { mem::transmute::<RefMut<'_, ()>, RefMut<'static, ()>>(guard) };
+        Ok(SubRefMut {
+            ptr: ptr.expect("`RefMut::map` calls the getter"),
+            _guard: guard,
+            _owner: owner,
+        })
+    }
+
+    /**
+       Project the borrow further down, to a subregion of its current target, like
+       [`RefMut::map`].
+
+       This is an associated function, so it doesn't shadow a method of the target.
+    */
+    pub fn map<V, F>(mut this: Self, f: F) -> SubRefMut<T, V>
+    where
+        V: ?Sized,
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        let ptr = f(&mut *this) as *mut V;
+        SubRefMut {
+            ptr,
+            _guard: this._guard,
+            _owner: this._owner,
+        }
+    }
+}
+
+impl<T, U> Deref for SubRefMut<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: the target is kept mutably borrowed and alive by the guard and the owner.
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T, U> DerefMut for SubRefMut<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut U {
+        // SAFETY: see `deref`.
+        unsafe { &mut *self.ptr }
+    }
+}
+
+/**
+* A projection to a field of the value in an `Rc<Cell<T>>`, which reads and writes only that
+* field, like a [`Cell`] of its own.
+*
+* It is usually created with the [`subrc_cell`](crate::subrc_cell) macro.
+*
+* # Example
+```rust
+# use std::{cell::Cell, rc::Rc};
+# use subrc::subrc_cell;
+#[derive(Clone, Copy)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+let rc = Rc::new(Cell::new(Point { x: 1, y: 2 }));
+let y = subrc_cell!(rc, Point, y);
+y.set(3);
+y.update(|y| y * 2);
+assert_eq!(y.get(), 6);
+assert_eq!(rc.get().x, 1);
+assert_eq!(rc.get().y, 6);
+```
+*/
+pub struct SubrcCell<T, U> {
+    ptr: *mut U,
+    owner: Rc<Cell<T>>,
+}
+
+impl<T, U> SubrcCell<T, U>
+where
+    T: Copy,
+    U: Copy,
+{
+    /**
+       Create a [`SubrcCell`] projecting the value in `owner` to the field returned by `getter`.
+       The [`subrc_cell`](crate::subrc_cell) macro does it safely.
+
+       # Safety
+       `getter` must be a plain (possibly nested) field access, such as `|t| &t.a.b`: it may not
+       go through an enum variant, an index nor any indirection. The field is located once, and
+       the value may be replaced by one where the same location holds something else entirely.
+
+       # Panics
+       Panics if `getter` doesn't return a reference to a subregion of the value it is given.
+    */
+    #[track_caller]
+    pub unsafe fn new_unchecked<F>(owner: Rc<Cell<T>>, getter: F) -> Self
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let t = owner.get();
+        let u = getter(&t);
+        let offset = match get_offset(&t, u) {
+            Ok(offset) => offset,
+            Err(e) => {
+                invalid_projection::<T, U>(e, &t, ((u as *const U).addr(), mem::size_of::<U>()))
+            }
+        };
+        let ptr = owner.as_ptr().cast::<u8>().add(offset).cast::<U>();
+        SubrcCell { ptr, owner }
+    }
+
+    /// Return a copy of the field.
+    pub fn get(&self) -> U {
+        // SAFETY: the field lies within the cell, which is kept alive by the owner. No reference
+        // into it can exist, since `Cell` never hands one out through a shared reference.
+        unsafe { self.ptr.read() }
+    }
+
+    /// Set the field to `value`, leaving the rest of the value untouched.
+    pub fn set(&self, value: U) {
+        // SAFETY: see `get`.
+        unsafe { self.ptr.write(value) }
+    }
+
+    /// Replace the field with `value`, and return the old value.
+    pub fn replace(&self, value: U) -> U {
+        let old = self.get();
+        self.set(value);
+        old
+    }
+
+    /// Update the field with the value returned by `f`, which is given the current value.
+    pub fn update<F>(&self, f: F)
+    where
+        F: FnOnce(U) -> U,
+    {
+        self.set(f(self.get()));
+    }
+
+    /// Get the owner of the projected value.
+    pub fn owner(&self) -> &Rc<Cell<T>> {
+        &self.owner
+    }
+}
+
+impl<T, U> Clone for SubrcCell<T, U> {
+    fn clone(&self) -> Self {
+        SubrcCell {
+            ptr: self.ptr,
+            owner: self.owner.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+
+    use crate::{SubRef, SubRefMut, SubrcCell};
+
+    struct State {
+        items: Vec<u32>,
+        name: String,
+    }
+
+    fn state() -> Rc<RefCell<State>> {
+        Rc::new(RefCell::new(State {
+            items: vec![1, 2, 3],
+            name: String::from("state"),
+        }))
+    }
+
+    #[test]
+    fn test_sub_ref() {
+        let rc = state();
+        let items = SubRef::new(rc.clone(), |state| &state.items);
+        let name = SubRef::map(SubRef::new(rc.clone(), |state| &state.name), |name| {
+            &name[1..]
+        });
+        assert_eq!(*items, [1, 2, 3]);
+        assert_eq!(&*name, "tate");
+        assert!(rc.try_borrow_mut().is_err());
+
+        drop((items, name));
+        assert!(rc.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    fn test_sub_ref_mut() {
+        let rc = state();
+        let mut item = SubRefMut::map(
+            SubRefMut::new(rc.clone(), |state| &mut state.items),
+            |items| &mut items[1],
+        );
+        *item = 42;
+        assert!(rc.try_borrow().is_err());
+        assert!(SubRef::try_new(rc.clone(), |state| &state.name).is_err());
+
+        drop(item);
+        assert_eq!(rc.borrow().items, [1, 42, 3]);
+    }
+
+    #[test]
+    fn test_sub_ref_outlives_rc() {
+        let sub = SubRef::new(state(), |state| &state.name);
+        assert_eq!(*sub, "state");
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn should_panic_for_conflicting_borrow() {
+        let rc = state();
+        let _items = SubRef::new(rc.clone(), |state| &state.items);
+        let _name = SubRefMut::new(rc, |state| &mut state.name);
+    }
+
+    #[derive(Clone, Copy)]
+    struct Pod {
+        a: u8,
+        b: (u16, [u32; 2]),
+    }
+
+    #[test]
+    fn test_subrc_cell() {
+        let rc = Rc::new(Cell::new(Pod {
+            a: 1,
+            b: (2, [3, 4]),
+        }));
+        let a = crate::subrc_cell!(rc, Pod, a);
+        let b1 = crate::subrc_cell!(rc, Pod, b.1);
+        a.update(|a| a + 10);
+        assert_eq!(b1.replace([5, 6]), [3, 4]);
+        assert_eq!(rc.get().a, 11);
+        assert_eq!(rc.get().b, (2, [5, 6]));
+
+        rc.set(Pod {
+            a: 7,
+            b: (8, [9, 10]),
+        });
+        assert_eq!(a.get(), 7);
+        assert_eq!(b1.clone().get(), [9, 10]);
+    }
+
+    #[test]
+    fn test_subrc_cell_unchecked() {
+        let rc = Rc::new(Cell::new(Pod {
+            a: 1,
+            b: (2, [3, 4]),
+        }));
+        let item = unsafe { SubrcCell::new_unchecked(rc.clone(), |pod| &pod.b.1[1]) };
+        item.set(42);
+        assert_eq!(rc.get().b.1, [3, 42]);
+        assert!(Rc::ptr_eq(item.owner(), &rc));
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_for_unrelated_cell_getter() {
+        static OTHER: u8 = 0;
+        let rc = Rc::new(Cell::new(Pod {
+            a: 1,
+            b: (2, [3, 4]),
+        }));
+        let _cell = unsafe { SubrcCell::new_unchecked(rc, |_| &OTHER) };
+    }
+}
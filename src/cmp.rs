@@ -0,0 +1,363 @@
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+/**
+ Two [`Subptr`]s are equal if their targets are, regardless of their owners and of where the
+ targets lie, like two [`Rc`](alloc::rc::Rc)s are. Their owners may even be of different types.
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::Subrc;
+let first = Subrc::new(Rc::new((1u8, 2u32)), |t| &t.1);
+let second = Subrc::new(Rc::new([2u32, 3]), |t| &t[0]);
+assert!(first == second);
+```
+*/
+impl<P, Q, U> PartialEq<Subptr<Q, U>> for Subptr<P, U>
+where
+    P: SharedOwner,
+    Q: SharedOwner,
+    U: ?Sized + Pointee + PartialEq,
+{
+    fn eq(&self, other: &Subptr<Q, U>) -> bool {
+        *self.get() == *other.get()
+    }
+}
+
+impl<P, U> Eq for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + Eq,
+{
+}
+
+impl<P, U> PartialOrd for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.get().partial_cmp(other.get())
+    }
+}
+
+impl<P, U> Ord for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get().cmp(other.get())
+    }
+}
+
+/// Like equality, hashing only depends on the target.
+impl<P, U> Hash for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state)
+    }
+}
+
+/**
+ A [`Subptr`] can be compared with a plain value of its target type, e.g. `subrc == 42`.
+*/
+impl<P, U> PartialEq<U> for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: Pointee + PartialEq,
+{
+    fn eq(&self, other: &U) -> bool {
+        *self.get() == *other
+    }
+}
+
+impl<P, U> PartialOrd<U> for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: Pointee + PartialOrd,
+{
+    fn partial_cmp(&self, other: &U) -> Option<Ordering> {
+        self.get().partial_cmp(other)
+    }
+}
+
+/**
+ A wrapper comparing and hashing [`Subptr`]s by identity: two of them are equal if they share the
+ same owned value, and point to the same target within it.
+
+ # Example
+```rust
+# use std::{collections::HashSet, rc::Rc};
+# use subrc::{ByPtr, Subrc};
+let rc = Rc::new([1, 1]);
+let first = ByPtr(Subrc::new(rc.clone(), |t| &t[0]));
+let second = ByPtr(Subrc::new(rc.clone(), |t| &t[1]));
+// equal values, but different targets
+assert_eq!(*first.0, *second.0);
+assert!(first != second);
+
+let set: HashSet<_> = [first.clone(), second, first].into_iter().collect();
+assert_eq!(set.len(), 2);
+```
+*/
+#[derive(Clone, Debug)]
+pub struct ByPtr<S>(pub S);
+
+impl<P, U> ByPtr<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    fn key(&self) -> (*const u8, *const U) {
+        let owner = &**self.0.owner() as *const P::Target as *const u8;
+        (owner, Subptr::as_ptr(&self.0))
+    }
+}
+
+impl<P, U> PartialEq for ByPtr<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    fn eq(&self, other: &Self) -> bool {
+        Subptr::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<P, U> Eq for ByPtr<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+}
+
+impl<P, U> PartialOrd for ByPtr<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders by the address of the owned value first, then by the address of the target.
+impl<P, U> Ord for ByPtr<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl<P, U> Hash for ByPtr<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+
+impl<S> Deref for ByPtr<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.0
+    }
+}
+
+/**
+ A wrapper comparing and hashing [`Subptr`]s by the value of their targets, which is what
+ [`Subptr`] does as well. It spells the intent out where [`ByPtr`] could be used instead.
+
+ # Example
+```rust
+# use std::{collections::HashSet, rc::Rc};
+# use subrc::{ByValue, Subrc};
+let rc = Rc::new([1, 1]);
+let first = ByValue(Subrc::new(rc.clone(), |t| &t[0]));
+let second = ByValue(Subrc::new(rc.clone(), |t| &t[1]));
+assert!(first == second);
+
+let set: HashSet<_> = [first, second].into_iter().collect();
+assert_eq!(set.len(), 1);
+```
+*/
+#[derive(Clone, Debug)]
+pub struct ByValue<S>(pub S);
+
+impl<P, U> PartialEq for ByValue<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<P, U> Eq for ByValue<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + Eq,
+{
+}
+
+impl<P, U> PartialOrd for ByValue<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<P, U> Ord for ByValue<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<P, U> Hash for ByValue<Subptr<P, U>>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<S> Deref for ByValue<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeSet, HashSet},
+        rc::Rc,
+        sync::Arc,
+    };
+
+    use crate::{ByPtr, ByValue, Subarc, Subrc};
+
+    // not `PartialEq` itself
+    struct Foo {
+        a: u32,
+        b: u32,
+        c: f32,
+    }
+
+    #[test]
+    fn test_eq_compares_targets() {
+        let rc = Rc::new(Foo {
+            a: 1,
+            b: 1,
+            c: f32::NAN,
+        });
+        let other = Rc::new(Foo { a: 2, b: 1, c: 0.0 });
+        let a = Subrc::new(rc.clone(), |foo| &foo.a);
+        assert!(a == Subrc::new(rc.clone(), |foo| &foo.b));
+        assert!(a == Subrc::new(other.clone(), |foo| &foo.b));
+        assert!(a != Subrc::new(other, |foo| &foo.a));
+
+        // even the same target isn't equal to itself if its value isn't
+        let c = Subrc::new(rc, |foo| &foo.c);
+        assert!(c != c.clone());
+    }
+
+    #[test]
+    fn test_eq_across_owners() {
+        let foo = Rc::new(Foo { a: 1, b: 2, c: 3.0 });
+        let b = Subrc::new(foo.clone(), |foo| &foo.b);
+        let array = Subrc::new(Rc::new([1u32, 2]), |t| &t[1]);
+        let arc = Subarc::new(Arc::new((2u32,)), |t| &t.0);
+        assert!(b == array);
+        assert!(array == b);
+        assert!(b == arc);
+        assert!(Subrc::new(foo, |foo| &foo.a) != array);
+    }
+
+    #[test]
+    fn test_cmp_with_values() {
+        let rc = Rc::new(Foo { a: 1, b: 2, c: 0.5 });
+        let a = Subrc::new(rc.clone(), |foo| &foo.a);
+        assert!(a == 1);
+        assert!(a != 2);
+        assert!(a < 2);
+        assert!(a >= 1);
+
+        let c = Subrc::new(rc, |foo| &foo.c);
+        assert!(c > 0.0);
+        assert!(c.partial_cmp(&f32::NAN).is_none());
+    }
+
+    #[test]
+    fn test_ord_and_hash() {
+        let rc = Rc::new([3u8, 1, 2, 1]);
+        let items: Vec<_> = (0..4).map(|i| Subrc::new(rc.clone(), |t| &t[i])).collect();
+
+        let set: BTreeSet<_> = items.iter().cloned().collect();
+        assert_eq!(set.iter().map(|item| **item).collect::<Vec<_>>(), [1, 2, 3]);
+
+        let set: HashSet<_> = items.into_iter().collect();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&Subrc::new(Rc::new([0, 0, 2, 0]), |t| &t[2])));
+    }
+
+    #[test]
+    fn test_by_ptr() {
+        let (rc, other) = (Rc::new([1u8, 1]), Rc::new([1u8, 1]));
+        let item = |rc: &Rc<[u8; 2]>, i: usize| ByPtr(Subrc::new(rc.clone(), |t| &t[i]));
+        assert!(item(&rc, 0) == item(&rc, 0));
+        assert!(item(&rc, 0) != item(&rc, 1));
+        assert!(item(&rc, 0) != item(&other, 0));
+        assert!(item(&rc, 0) < item(&rc, 1));
+
+        // same address, different lengths
+        let all = ByPtr(Subrc::<_, [u8]>::new(rc.clone(), |t| &t[..]));
+        let head = ByPtr(Subrc::<_, [u8]>::new(rc.clone(), |t| &t[..1]));
+        assert!(all != head);
+
+        let set: BTreeSet<_> = [item(&rc, 1), item(&rc, 0), item(&rc, 1)]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_by_value() {
+        let (rc, other) = (Rc::new([1u8, 2]), Rc::new([1u8, 2]));
+        let item = |rc: &Rc<[u8; 2]>, i: usize| ByValue(Subrc::new(rc.clone(), |t| &t[i]));
+        assert!(item(&rc, 0) == item(&other, 0));
+        assert!(item(&rc, 0) < item(&other, 1));
+
+        let set: HashSet<_> = [item(&rc, 1), item(&other, 1)].into_iter().collect();
+        assert_eq!(set.len(), 1);
+        assert_eq!(*set.iter().next().unwrap().0, 2);
+    }
+}
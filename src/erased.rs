@@ -0,0 +1,269 @@
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+use core::any::Any;
+
+use crate::{Pointee, SharedOwner, Subarc, Subptr, Subrc};
+
+/**
+* A [`Subrc`] whose owner type is erased, so it only exposes the projected `U`.
+*
+* It is created by [`Subrc::erase_owner`], and the typed [`Subrc`] can be recovered with
+* [`downcast_owner`](Subptr::downcast_owner). This keeps the concrete owner out of APIs, e.g. in
+* plugin systems where every plugin owns a different value.
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{ErasedSubrc, Subrc};
+struct Plugin {
+    name: String,
+}
+
+let rc = Rc::new(Plugin { name: String::from("spell checker") });
+let name: ErasedSubrc<String> = Subrc::erase_owner(Subrc::new(rc.clone(), |p| &p.name));
+assert_eq!(*name, "spell checker");
+
+let name: Subrc<Plugin, String> = ErasedSubrc::downcast_owner(name).ok().unwrap();
+assert!(std::ptr::eq(&*name, &rc.name));
+```
+*/
+pub type ErasedSubrc<U> = Subrc<dyn Any, U>;
+
+/// The thread-safe counterpart of [`ErasedSubrc`], created by [`Subarc::erase_owner`].
+pub type ErasedSubarc<U> = Subarc<dyn Any + Send + Sync, U>;
+
+impl<T: Any, U: ?Sized + Pointee> Subptr<Rc<T>, U> {
+    /// Erase the owner type of the projection, see [`ErasedSubrc`].
+    pub fn erase_owner(this: Self) -> ErasedSubrc<U> {
+        let (owner, ptr) = Subptr::into_raw_parts(this);
+        // SAFETY: the owner is the same `Rc`, only its type is erased.
+        unsafe { Subptr::from_raw_parts(owner as Rc<dyn Any>, ptr) }
+    }
+}
+
+impl<U: ?Sized + Pointee> Subptr<Rc<dyn Any>, U> {
+    /**
+       Recover the typed [`Subrc`] from an [`ErasedSubrc`], if its owned value is a `T`.
+
+       Returns the projection back otherwise.
+    */
+    pub fn downcast_owner<T: Any>(this: Self) -> Result<Subrc<T, U>, Self> {
+        let (owner, ptr) = Subptr::into_raw_parts(this);
+        // SAFETY: the owner is the same `Rc` either way, only its type changes.
+        match owner.downcast::<T>() {
+            Ok(owner) => Ok(unsafe { Subptr::from_raw_parts(owner, ptr) }),
+            Err(owner) => Err(unsafe { Subptr::from_raw_parts(owner, ptr) }),
+        }
+    }
+
+    /**
+       Downcast an erased `owner` to a `T`, and project it with `getter` in one step, like
+       [`new`](Subptr::new). This is handy for heterogeneous registries of `Rc<dyn Any>`.
+
+       Returns the owner back if its owned value is not a `T`.
+
+       # Panics
+       Like [`new`](Subptr::new), `getter` must return a subregion of the owned value.
+
+       ## Example
+       ```rust
+           # use std::{any::Any, rc::Rc};
+           # use subrc::Subrc;
+           struct Window {
+               title: String,
+           }
+
+           let window = Window { title: String::from("main") };
+           let registry: Vec<Rc<dyn Any>> = vec![Rc::new(42), Rc::new(window)];
+           let titles: Vec<Subrc<Window, String>> = registry
+               .into_iter()
+               .filter_map(|rc| Subrc::downcast_project::<Window, _>(rc, |w| &w.title).ok())
+               .collect();
+           assert_eq!(*titles[0], "main");
+       ```
+    */
+    pub fn downcast_project<T, F>(owner: Rc<dyn Any>, getter: F) -> Result<Subrc<T, U>, Rc<dyn Any>>
+    where
+        T: Any,
+        F: FnOnce(&T) -> &U,
+    {
+        Ok(Subptr::new(owner.downcast::<T>()?, getter))
+    }
+}
+
+impl<T: Any + Send + Sync, U: ?Sized + Pointee> Subptr<Arc<T>, U> {
+    /// Erase the owner type of the projection, see [`ErasedSubarc`].
+    pub fn erase_owner(this: Self) -> ErasedSubarc<U> {
+        let (owner, ptr) = Subptr::into_raw_parts(this);
+        // SAFETY: the owner is the same `Arc`, only its type is erased.
+        unsafe { Subptr::from_raw_parts(owner as Arc<dyn Any + Send + Sync>, ptr) }
+    }
+}
+
+impl<U: ?Sized + Pointee> Subptr<Arc<dyn Any + Send + Sync>, U> {
+    /// Recover the typed [`Subarc`] from an [`ErasedSubarc`], like
+    /// [`downcast_owner`](Subptr::downcast_owner) does for an [`ErasedSubrc`].
+    pub fn downcast_owner<T: Any + Send + Sync>(this: Self) -> Result<Subarc<T, U>, Self> {
+        let (owner, ptr) = Subptr::into_raw_parts(this);
+        // SAFETY: the owner is the same `Arc` either way, only its type changes.
+        match owner.downcast::<T>() {
+            Ok(owner) => Ok(unsafe { Subptr::from_raw_parts(owner, ptr) }),
+            Err(owner) => Err(unsafe { Subptr::from_raw_parts(owner, ptr) }),
+        }
+    }
+
+    /// Downcast an erased `owner` to a `T`, and project it with `getter` in one step, like
+    /// [`downcast_project`](Subptr::downcast_project) does for an `Rc<dyn Any>`.
+    pub fn downcast_project<T, F>(
+        owner: Arc<dyn Any + Send + Sync>,
+        getter: F,
+    ) -> Result<Subarc<T, U>, Arc<dyn Any + Send + Sync>>
+    where
+        T: Any + Send + Sync,
+        F: FnOnce(&T) -> &U,
+    {
+        Ok(Subptr::new(owner.downcast::<T>()?, getter))
+    }
+}
+
+mod sealed {
+    use super::*;
+
+    /// A `dyn Any` target, or a boxed one, which [`Subptr::downcast`] can look into.
+    pub trait AnyTarget {
+        fn downcast_ptr<V: Any>(&self) -> Option<*const V>;
+    }
+
+    macro_rules! impl_any_target {
+        ($any:ty) => {
+            impl AnyTarget for $any {
+                fn downcast_ptr<V: Any>(&self) -> Option<*const V> {
+                    self.downcast_ref::<V>().map(|value| value as *const V)
+                }
+            }
+
+            impl AnyTarget for Box<$any> {
+                fn downcast_ptr<V: Any>(&self) -> Option<*const V> {
+                    (**self).downcast_ptr()
+                }
+            }
+        };
+    }
+
+    impl_any_target!(dyn Any);
+    impl_any_target!(dyn Any + Send);
+    impl_any_target!(dyn Any + Send + Sync);
+
+    #[cfg(feature = "reflect")]
+    impl AnyTarget for dyn crate::Reflect {
+        fn downcast_ptr<V: Any>(&self) -> Option<*const V> {
+            (self as &dyn Any).downcast_ptr()
+        }
+    }
+}
+
+impl<P, A> Subptr<P, A>
+where
+    P: SharedOwner,
+    A: ?Sized + Pointee + sealed::AnyTarget,
+{
+    /**
+       Recover a projection to the concrete type of a `dyn Any` (or `Box<dyn Any>`, or `dyn
+       Reflect` with the `reflect` feature) target, if it is a `V`, like `downcast_ref` does for a
+       reference to [`Any`]. A boxed value lies behind an indirection, see
+       [`new_indirect`](Subptr::new_indirect).
+
+       Returns the projection back otherwise.
+
+       ## Example
+       ```rust
+           # use std::{any::Any, rc::Rc};
+           # use subrc::Subrc;
+           let rc = Rc::new((1u8, 2u32));
+           let any = Subrc::<_, dyn Any>::new(rc.clone(), |t| &t.1);
+           let any = Subrc::downcast::<u8>(any).err().unwrap();
+           let value = Subrc::downcast::<u32>(any).ok().unwrap();
+           assert!(std::ptr::eq(&*value, &rc.1));
+       ```
+    */
+    pub fn downcast<V: Any>(this: Self) -> Result<Subptr<P, V>, Self> {
+        match this.downcast_ptr::<V>() {
+            // SAFETY: the target is a `V`, or the box holds one, which is kept alive and in place
+            // by the owned value, see `new_indirect`.
+            Some(ptr) => Ok(unsafe { Subptr::from_raw_parts(Subptr::into_owner(this), ptr) }),
+            None => Err(this),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{any::Any, rc::Rc, sync::Arc};
+
+    use crate::{ErasedSubarc, ErasedSubrc, Subarc, Subrc};
+
+    #[test]
+    fn test_erase_and_downcast_owner() {
+        let rc = Rc::new((1u8, 2u32));
+        let erased: ErasedSubrc<u32> = Subrc::erase_owner(Subrc::new(rc.clone(), |t| &t.1));
+        assert!(std::ptr::eq(&*erased, &rc.1));
+        assert_eq!(Rc::strong_count(&rc), 2);
+
+        let erased = ErasedSubrc::downcast_owner::<(u8, u16)>(erased)
+            .err()
+            .unwrap();
+        let typed: Subrc<(u8, u32), u32> = ErasedSubrc::downcast_owner(erased).ok().unwrap();
+        assert!(std::ptr::eq(&*typed, &rc.1));
+        assert!(Rc::ptr_eq(typed.owner(), &rc));
+    }
+
+    #[test]
+    fn test_erased_subarc() {
+        let arc = Arc::new((1u8, 2u32));
+        let erased: ErasedSubarc<u32> = Subarc::erase_owner(Subarc::new(arc.clone(), |t| &t.1));
+        std::thread::spawn(move || {
+            let typed: Subarc<(u8, u32), u32> = ErasedSubarc::downcast_owner(erased).ok().unwrap();
+            assert_eq!(*typed, 2);
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_downcast() {
+        let arc = Arc::new((1u8, 2u32));
+        let any = Subarc::<_, dyn Any + Send + Sync>::new(arc.clone(), |t| &t.0);
+        let any = Subarc::downcast::<u32>(any).err().unwrap();
+        let value = Subarc::downcast::<u8>(any).ok().unwrap();
+        assert!(std::ptr::eq(&*value, &arc.0));
+    }
+
+    #[test]
+    fn test_downcast_boxed() {
+        let rc = Rc::new((1u8, Box::new(String::from("two")) as Box<dyn Any>));
+        let boxed = Subrc::new(rc.clone(), |t| &t.1);
+        let boxed = Subrc::downcast::<&str>(boxed).err().unwrap();
+        let value = Subrc::downcast::<String>(boxed).ok().unwrap();
+        assert_eq!(*value, "two");
+        assert!(std::ptr::eq(
+            &*value,
+            rc.1.downcast_ref::<String>().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_downcast_project() {
+        let rc: Rc<dyn Any> = Rc::new((1u8, 2u32));
+        let rc = Subrc::downcast_project::<(u8, u16), _>(rc, |t| &t.1)
+            .err()
+            .unwrap();
+        let value = Subrc::downcast_project::<(u8, u32), _>(rc.clone(), |t| &t.1).unwrap();
+        assert!(std::ptr::eq(
+            &*value,
+            &rc.downcast_ref::<(u8, u32)>().unwrap().1
+        ));
+
+        let arc: Arc<dyn Any + Send + Sync> = Arc::new((1u8, 2u32));
+        let value = Subarc::downcast_project::<(u8, u32), _>(arc, |t| &t.0).unwrap();
+        assert_eq!(*value, 1);
+    }
+}
@@ -0,0 +1,80 @@
+use core::{error::Error, fmt};
+
+/**
+ The reason why a getter's returned reference could not be turned into a [`Subptr`](crate::Subptr).
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProjectionError {
+    /// The reference points before the start of the owned value.
+    BeforeOwner,
+    /// The reference points, or extends, past the end of the owned value.
+    OutOfBounds,
+    /// The reference is not properly aligned for its type.
+    Misaligned,
+    /// The reference is too far into the owned value, or too long, to be stored in a
+    /// [`SmallSubptr`](crate::SmallSubptr).
+    TooLarge,
+}
+
+impl fmt::Display for ProjectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectionError::BeforeOwner => {
+                write!(f, "reference points before the start of the owned value")
+            }
+            ProjectionError::OutOfBounds => {
+                write!(f, "reference extends past the end of the owned value")
+            }
+            ProjectionError::Misaligned => write!(f, "reference is misaligned"),
+            ProjectionError::TooLarge => write!(f, "reference is too large to be stored compactly"),
+        }
+    }
+}
+
+impl Error for ProjectionError {}
+
+/**
+ The reason why a range of bytes could not be viewed as a typed value, see
+ [`Subptr::project_as`](crate::Subptr::project_as).
+*/
+#[cfg(feature = "zerocopy")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CastError {
+    /// The range is out of the bounds of the buffer.
+    OutOfRange,
+    /// The bytes are not properly aligned for the type.
+    Misaligned,
+    /// The bytes don't have the size of the type, or of a whole number of its elements.
+    SizeMismatch,
+}
+
+#[cfg(feature = "zerocopy")]
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CastError::OutOfRange => write!(f, "range is out of the bounds of the buffer"),
+            CastError::Misaligned => write!(f, "bytes are misaligned for the type"),
+            CastError::SizeMismatch => write!(f, "bytes don't have the size of the type"),
+        }
+    }
+}
+
+#[cfg(feature = "zerocopy")]
+impl Error for CastError {}
+
+/**
+ The error returned when mutable access to the target of a [`Subptr`](crate::Subptr) is refused,
+ because the owned value is shared, or because the target lives behind an indirection.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotUnique;
+
+impl fmt::Display for NotUnique {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the owned value is not uniquely owned")
+    }
+}
+
+impl Error for NotUnique {}
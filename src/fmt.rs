@@ -0,0 +1,138 @@
+use core::{any::type_name, fmt};
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+/**
+ Formats the target, like a reference to it would.
+
+ The alternate form (`{:#?}`) also shows the type of the owner, the offset of the target within the
+ owned value (`None` if it lives behind an indirection) and the number of owners, for diagnostics.
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::Subrc;
+let subrc = Subrc::new(Rc::new((1u8, [2u8, 3])), |t| &t.1);
+assert_eq!(format!("{:?}", subrc), "[2, 3]");
+assert!(format!("{:#?}", subrc).contains("offset: Some(1)"));
+```
+*/
+impl<P, U> fmt::Debug for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !f.alternate() {
+            return fmt::Debug::fmt(self.get(), f);
+        }
+        let offset = Subptr::projection_offset(self);
+        f.debug_struct("Subptr")
+            .field("owner", &type_name::<P>())
+            .field("offset", &format_args!("{:?}", offset))
+            .field("strong_count", &P::strong_count(self.owner()))
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+/**
+ Formats the target, so that e.g. a `Subrc<Config, String>` can be printed without dereferencing
+ it first.
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::Subrc;
+let subrc = Subrc::new(Rc::new((1u8, String::from("two"))), |t| &t.1);
+assert_eq!(format!("{subrc:>5}"), "  two");
+```
+*/
+impl<P, U> fmt::Display for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.get(), f)
+    }
+}
+
+/**
+ Formats the address of the target, like formatting a reference to it with `{:p}` would.
+
+ The alternate form (`{:#p}`) shows it as the address of the owned value plus the offset of the
+ target within it, unless the target lives behind an indirection.
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::Subrc;
+let rc = Rc::new((1u32, 2u8));
+let subrc = Subrc::new(rc.clone(), |t| &t.1);
+assert_eq!(format!("{:p}", subrc), format!("{:p}", &rc.1));
+assert_eq!(format!("{:#p}", subrc), format!("{:p}+0x4", &*rc));
+```
+*/
+impl<P, U> fmt::Pointer for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ptr = Subptr::as_ptr(self);
+        if f.alternate() {
+            let owner = &**self.owner() as *const P::Target;
+            if let Some(offset) = Subptr::projection_offset(self) {
+                return write!(f, "{:p}+{:#x}", owner, offset);
+            }
+        }
+        write!(f, "{:p}", ptr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::Subrc;
+
+    #[test]
+    fn test_debug() {
+        let rc = Rc::new((1u8, String::from("two")));
+        let subrc = Subrc::new(rc.clone(), |t| &t.1);
+        assert_eq!(format!("{:?}", subrc), format!("{:?}", "two"));
+
+        let alternate = format!("{:#?}", subrc);
+        assert!(alternate.contains("Rc<(u8, alloc::string::String)>"));
+        assert!(alternate.contains(&format!(
+            "offset: Some({}),",
+            std::mem::offset_of!((u8, String), 1)
+        )));
+        assert!(alternate.contains("strong_count: 2,"));
+        assert!(alternate.contains("value: \"two\","));
+
+        let indirect = Subrc::new_indirect(rc, |t| t.1.as_str());
+        assert!(format!("{:#?}", indirect).contains("offset: None,"));
+    }
+
+    #[test]
+    fn test_display() {
+        let subrc = Subrc::new(Rc::new((1u8, 2.5f32)), |t| &t.1);
+        assert_eq!(subrc.to_string(), "2.5");
+        assert_eq!(format!("{:.2}", subrc), "2.50");
+    }
+
+    #[test]
+    fn test_pointer() {
+        let rc = Rc::new((1u8, String::from("two")));
+        let subrc = Subrc::new(rc.clone(), |t| &t.1);
+        assert_eq!(format!("{:p}", subrc), format!("{:p}", &rc.1));
+        let offset = std::mem::offset_of!((u8, String), 1);
+        assert_eq!(format!("{:#p}", subrc), format!("{:p}+{:#x}", &*rc, offset));
+
+        let indirect = Subrc::new_indirect(rc.clone(), |t| t.1.as_str());
+        assert_eq!(format!("{:p}", indirect), format!("{:p}", rc.1.as_str()));
+        assert_eq!(format!("{:#p}", indirect), format!("{:p}", rc.1.as_str()));
+    }
+}
@@ -0,0 +1,141 @@
+use ::gc::{custom_trace, Finalize, Gc, Trace};
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+/**
+* A pointer to a sub-region (member) of the value behind a garbage collected [`Gc`].
+*
+* It keeps the owned value alive like the [`Gc`] it holds, and is itself [`Trace`], so it can be
+* stored in another garbage collected value, including one the owned value points back to. Like
+* any [`Gc`] stored in a garbage collected value, its target must not be accessed while that value
+* is being dropped, since the owned value may already have been collected.
+*
+* A [`Gc`] never gives mutable access to its value, nor counts its owners, so the projection is
+* never unique: [`strong_count`](Subptr::strong_count) is always 1, only counting the projection's
+* own owner, [`weak_count`](Subptr::weak_count) is always 0, [`is_unique`](Subptr::is_unique) is
+* always false, and
+* [`get_mut`](Subptr::get_mut) and [`try_unwrap_owner`](Subptr::try_unwrap_owner) always fail.
+*
+* # Panics
+* [`make_mut`](Subptr::make_mut) always panics, as a [`Gc`] doesn't give mutable access to a
+* clone of its value either.
+*
+* # Example
+```rust
+# use gc::{Finalize, Gc, GcCell, Trace};
+# use subrc::SubGc;
+#[derive(Trace, Finalize)]
+struct Node {
+    name: String,
+    parent: GcCell<Option<SubGc<Node, String>>>,
+}
+
+let root = Gc::new(Node { name: String::from("root"), parent: GcCell::new(None) });
+let child = Gc::new(Node { name: String::from("child"), parent: GcCell::new(None) });
+*child.parent.borrow_mut() = Some(SubGc::new(root.clone(), |node| &node.name));
+assert_eq!(**child.parent.borrow().as_ref().unwrap(), "root");
+```
+*/
+pub type SubGc<T, U> = Subptr<Gc<T>, U>;
+
+// SAFETY: the value behind a `Gc` is never moved nor mutated through it, and is only collected
+// once no `Gc` to it is reachable. The projections trace their owner, so it stays reachable.
+unsafe impl<T: Trace + ?Sized> SharedOwner for Gc<T> {
+    // A `Gc` doesn't count its owners, so only this one is known to be alive, and it may not be
+    // the only one.
+    fn strong_count(_this: &Self) -> usize {
+        1
+    }
+
+    fn weak_count(_this: &Self) -> usize {
+        0
+    }
+
+    fn is_unique(_this: &Self) -> bool {
+        false
+    }
+
+    fn try_unwrap(this: Self) -> Result<Self::Target, Self>
+    where
+        Self::Target: Sized,
+    {
+        Err(this)
+    }
+
+    fn get_mut(_this: &mut Self) -> Option<&mut Self::Target> {
+        None
+    }
+
+    fn make_mut(_this: &mut Self) -> &mut Self::Target
+    where
+        Self::Target: Clone,
+    {
+        panic!("a `Gc` never gives mutable access to its value")
+    }
+}
+
+impl<T: Trace + ?Sized, U: ?Sized + Pointee> Finalize for Subptr<Gc<T>, U> {}
+
+// SAFETY: the only `Gc` held by the projection is its owner, which is traced.
+unsafe impl<T: Trace + ?Sized, U: ?Sized + Pointee> Trace for Subptr<Gc<T>, U> {
+    custom_trace!(this, mark(this.owner()));
+}
+
+#[cfg(test)]
+// the `Trace` derive nests its impls, which the lint flags
+#[allow(non_local_definitions)]
+mod tests {
+    use ::gc::{force_collect, Finalize, Gc, GcCell, Trace};
+
+    use crate::SubGc;
+
+    #[derive(Trace, Finalize)]
+    struct Node {
+        value: u32,
+        other: GcCell<Option<SubGc<Node, u32>>>,
+    }
+
+    fn node(value: u32) -> Gc<Node> {
+        Gc::new(Node {
+            value,
+            other: GcCell::new(None),
+        })
+    }
+
+    #[test]
+    fn test_sub_gc_keeps_owner_alive() {
+        let holder = node(0);
+        *holder.other.borrow_mut() = Some(SubGc::new(node(1), |node| &node.value));
+        force_collect();
+        assert_eq!(**holder.other.borrow().as_ref().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_sub_gc_cycle() {
+        let (a, b) = (node(1), node(2));
+        *a.other.borrow_mut() = Some(SubGc::new(b.clone(), |node| &node.value));
+        *b.other.borrow_mut() = Some(SubGc::new(a.clone(), |node| &node.value));
+        let value = SubGc::new(a.clone(), |node| &node.value);
+        drop((a, b));
+        force_collect();
+        assert_eq!(*value, 1);
+        drop(value);
+        force_collect();
+    }
+
+    #[test]
+    fn test_sub_gc_never_unique() {
+        let mut value = SubGc::new(node(1), |node| &node.value);
+        assert_eq!(SubGc::strong_count(&value), 1);
+        assert!(!SubGc::is_unique(&value));
+        assert!(SubGc::get_mut(&mut value).is_none());
+        assert!(SubGc::try_unwrap_owner(value).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "a `Gc` never gives mutable access to its value")]
+    fn test_sub_gc_make_mut_panics() {
+        let mut value = SubGc::new(Gc::new((1u32, 2u32)), |t| &t.1);
+        *SubGc::make_mut(&mut value) += 1;
+    }
+}
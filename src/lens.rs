@@ -0,0 +1,141 @@
+use crate::{Pointee, ProjectionError, SharedOwner, Subptr};
+
+/**
+* An accessor to a part `U` of a value `T`, shaped after the read half of `druid::Lens`, so the
+* lenses defined for a GUI framework can implement it in a few lines, and then drive
+* [`Subptr::from_lens`] instead of duplicating their getters.
+*
+* Unlike a `druid::Lens`, which only lends the part to a closure, it must return the part for as
+* long as `data` is borrowed, since the projection keeps pointing to it. A lens can then only
+* access a part stored in `data`, and not e.g. one guarded by a [`RefCell`](core::cell::RefCell)
+* borrow, which ends with the call:
+```compile_fail
+# use std::cell::RefCell;
+# use subrc::Lens;
+struct Buffer;
+
+impl Lens<RefCell<Vec<u8>>, [u8]> for Buffer {
+    fn get<'t>(&self, data: &'t RefCell<Vec<u8>>) -> &'t [u8] {
+        &data.borrow()[..]
+    }
+}
+```
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{Lens, Subrc};
+struct Settings {
+    volume: u8,
+}
+
+struct Volume;
+
+impl Lens<Settings, u8> for Volume {
+    fn get<'t>(&self, data: &'t Settings) -> &'t u8 {
+        &data.volume
+    }
+}
+
+let rc = Rc::new(Settings { volume: 7 });
+let volume = Subrc::from_lens(rc.clone(), Volume);
+assert!(std::ptr::eq(&*volume, &rc.volume));
+assert_eq!(Volume.with(&rc, |volume| volume + 1), 8);
+```
+*/
+pub trait Lens<T: ?Sized, U: ?Sized> {
+    /// Get the part of `data` this lens accesses.
+    fn get<'t>(&self, data: &'t T) -> &'t U;
+
+    /// Call `f` with the part of `data` this lens accesses, like `druid::Lens::with`.
+    fn with<V, F: FnOnce(&U) -> V>(&self, data: &T, f: F) -> V {
+        f(self.get(data))
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, L: Lens<T, U> + ?Sized> Lens<T, U> for &L {
+    fn get<'t>(&self, data: &'t T) -> &'t U {
+        (**self).get(data)
+    }
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    /**
+       Create a [`Subptr`] pointing to the part of the owned value accessed by `lens`, like
+       [`new`](Subptr::new) does with a getter.
+
+       # Panics
+       Like [`new`](Subptr::new), the lens must access a subregion of the owned value, and not e.g.
+       a `static`. Use [`try_from_lens`](Subptr::try_from_lens) to handle this case
+       gracefully.
+    */
+    #[track_caller]
+    pub fn from_lens<L: Lens<P::Target, U>>(owner: P, lens: L) -> Self {
+        Self::new(owner, |t| lens.get(t))
+    }
+
+    /**
+       Like [`from_lens`](Subptr::from_lens), but returns a [`ProjectionError`] instead of
+       panicking if `lens` does not access a subregion of the owned value.
+    */
+    pub fn try_from_lens<L: Lens<P::Target, U>>(
+        owner: P,
+        lens: L,
+    ) -> Result<Self, ProjectionError> {
+        Self::try_new(owner, |t| lens.get(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::Lens;
+    use crate::Subrc;
+
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    struct X;
+
+    impl Lens<Point, i32> for X {
+        fn get<'t>(&self, data: &'t Point) -> &'t i32 {
+            &data.x
+        }
+    }
+
+    struct Origin;
+
+    impl Lens<Point, i32> for Origin {
+        fn get<'t>(&self, _data: &'t Point) -> &'t i32 {
+            static ORIGIN: i32 = 0;
+            &ORIGIN
+        }
+    }
+
+    #[test]
+    fn test_from_lens() {
+        let rc = Rc::new(Point { x: 1, y: 2 });
+        let x = Subrc::from_lens(rc.clone(), &X);
+        assert!(std::ptr::eq(&*x, &rc.x));
+        assert_eq!(X.with(&rc, |x| x + rc.y), 3);
+    }
+
+    #[test]
+    fn test_from_outside_lens() {
+        let rc = Rc::new(Point { x: 1, y: 2 });
+        assert!(Subrc::try_from_lens(rc, Origin).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_outside_lens_panics() {
+        Subrc::from_lens(Rc::new(Point { x: 1, y: 2 }), Origin);
+    }
+}
@@ -1,10 +1,118 @@
-use std::{marker::PhantomData, ops::Deref, rc::Rc};
+#![cfg_attr(not(test), no_std)]
+#![cfg_attr(
+    feature = "nightly",
+    feature(coerce_unsized, get_mut_unchecked, unsize)
+)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![cfg_attr(
+    feature = "nightly",
+    feature(strict_provenance_lints),
+    warn(fuzzy_provenance_casts, lossy_provenance_casts)
+)]
+
+extern crate alloc;
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+use alloc::{rc::Rc, sync::Arc};
+
+#[cfg(feature = "arc-swap")]
+mod atomic;
+mod bytes;
+mod cell;
+mod cmp;
+mod erased;
+mod error;
+mod fmt;
+#[cfg(feature = "gc")]
+mod gc;
+mod lens;
+mod map;
+mod once;
+mod owner;
+mod pair;
+#[cfg(feature = "parking_lot")]
+pub mod parking_lot;
+mod pin;
+mod pointee;
+mod project;
+mod projectable;
+pub mod projection;
+pub mod projector;
+#[cfg(feature = "std")]
+mod raw;
+#[cfg(feature = "reflect")]
+mod reflect;
+mod registry;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "serde")]
+mod serde;
+pub mod slice;
+mod small;
+#[cfg(feature = "stable_deref_trait")]
+mod stable_deref;
+mod string;
+mod subptr;
+#[cfg(feature = "std")]
+mod sync;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+mod unaligned;
+mod weak;
+#[cfg(feature = "yoke")]
+mod yoke;
+#[cfg(feature = "zerocopy")]
+mod zerocopy;
+
+#[cfg(feature = "arc-swap")]
+pub use atomic::AtomicSubarc;
+#[cfg(feature = "gc")]
+pub use gc::SubGc;
+#[cfg(feature = "derive")]
+pub use subrc_derive::Project;
+
+pub use cell::{SubRef, SubRefMut, SubrcCell};
+pub use cmp::{ByPtr, ByValue};
+pub use erased::{ErasedSubarc, ErasedSubrc};
+#[cfg(feature = "zerocopy")]
+pub use error::CastError;
+pub use error::{NotUnique, ProjectionError};
+pub use lens::Lens;
+pub use map::Lookup;
+pub use owner::{SharedOwner, WeakOwner};
+pub use pair::SubptrPair;
+pub use pointee::{DynMetadata, Pointee};
+pub use project::{FieldOffset, Project, ProjectExt};
+pub use projectable::Projectable;
+pub use projection::Projection;
+pub use projector::Projector;
+#[cfg(feature = "reflect")]
+pub use reflect::Reflect;
+pub use registry::{ProjectionKey, ProjectionRegistry};
+pub use slice::SubptrSlice;
+pub use small::SmallSubptr;
+pub use string::SubptrStr;
+pub use subptr::Subptr;
+#[cfg(feature = "std")]
+pub use sync::{SubMutex, SubMutexGuard, SubRwLock, SubRwLockReadGuard, SubRwLockWriteGuard};
+pub use unaligned::SubptrUnaligned;
+pub use weak::WeakSubptr;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{rc::Rc, sync::Arc};
+
+    pub use crate::weak::weak_from_field_offset;
+}
 
 /**
 * A reference counted pointer to a sub-region (member) of a [`Rc`].
 *
 * # Example
 ```rust
+# use std::rc::Rc;
+# use subrc::{subrc, Subrc};
 struct Foo {
     value: i32,
 }
@@ -18,83 +126,89 @@ assert_eq!(*subrc, 42);
 assert!(std::ptr::eq(&*subrc, &rc.value));
 ```
 */
-#[derive(PartialEq, Clone)]
-pub struct Subrc<T, U> {
-    rc: Rc<T>,
-    offset: usize,
-    #[doc(hidden)]
-    _u: PhantomData<U>,
+pub type Subrc<T, U> = Subptr<Rc<T>, U>;
+
+/**
+* A thread-safe reference counted pointer to a sub-region (member) of an [`Arc`].
+*
+* This is the [`Arc`] counterpart of [`Subrc`]: it is [`Send`] and [`Sync`] whenever the owner `T`
+* and the projected `U` are, so it can be handed out across threads.
+*
+* # Example
+```rust
+# use std::sync::Arc;
+# use subrc::{subarc, Subarc};
+struct Foo {
+    value: i32,
 }
 
-impl<T, U> Subrc<T, U> {
-    unsafe fn get_offset(t: &T, u: &U) -> usize {
-        let t_ptr = t as *const T as usize;
-        let u_ptr = u as *const U as usize;
+let arc = Arc::new(Foo { value: 42 });
+let subarc = Subarc::new(arc.clone(), |foo| &foo.value);
+// (or use the macro: `subarc!(arc.value)` )
+std::thread::spawn(move || {
+    // subarc derefs to 42
+    assert_eq!(*subarc, 42);
+})
+.join()
+.unwrap();
+```
+*/
+pub type Subarc<T, U> = Subptr<Arc<T>, U>;
 
-        if u_ptr < t_ptr {
-            panic!("getter did not return portion of the object");
-        }
+/// A [`Subrc`] to a slice, see [`SubptrSlice`].
+pub type SubrcSlice<T, U> = SubptrSlice<Rc<T>, U>;
 
-        let offset = u_ptr - t_ptr;
-        if offset >= std::mem::size_of::<T>() {
-            panic!("getter did not return portion of the object");
-        }
+/// A [`Subarc`] to a slice, see [`SubptrSlice`].
+pub type SubarcSlice<T, U> = SubptrSlice<Arc<T>, U>;
 
-        offset
-    }
+/// A [`Subrc`] to a [`str`], see [`SubptrStr`].
+pub type SubrcStr<T> = SubptrStr<Rc<T>>;
 
-    /**
-       Create a [`Subrc`] pointer, which points to a subregion of the specified [`Rc`].
-       The `getter` function is used to specify the subregion. It must return a reference to a subregion
-       of the [`Rc`]. Returning anything else will result in a panic.
-
-       It is recommended to use the [`subrc`] macro for better readability and an extra safe guard to
-       prevent panicking (by accidentally referencing something unrelated).
-
-       # Panics
-       In the `getter` function, returning anything other than a reference to a subregion of the [`Rc`]
-       will result in a panic.
-
-       ## Example
-       ```rust
-           let s = String::from("hello");
-           let rc = Rc::new(s);
-           let subrc = Subrc::new(rc.clone(), |s| &123);   // panic here: `123` is totally unrelated to `s`!
-       ```
-    */
-    pub fn new<F>(rc: Rc<T>, getter: F) -> Self
-    where
-        F: FnOnce(&T) -> &U,
-    {
-        let offset = unsafe { Self::get_offset(&*rc, getter(&rc)) };
-        Subrc {
-            rc,
-            offset,
-            _u: PhantomData,
-        }
-    }
+/// A [`Subarc`] to a [`str`], see [`SubptrStr`].
+pub type SubarcStr<T> = SubptrStr<Arc<T>>;
 
-    pub fn get(&self) -> &U {
-        unsafe {
-            let t_ptr = &*self.rc as *const T as *const u8;
-            let u_ptr = t_ptr.add(self.offset);
-            &*(u_ptr as *const U)
-        }
-    }
-}
+/// A [`Subrc`] to a range of bytes of a buffer, see [`Subptr::project_range`].
+pub type SubrcBytes<T> = Subrc<T, [u8]>;
 
-impl<T, U> Deref for Subrc<T, U> {
-    type Target = U;
+/**
+ A [`Subarc`] to a range of bytes of a buffer, see [`Subptr::project_range`].
 
-    fn deref(&self) -> &Self::Target {
-        self.get()
-    }
-}
+ With the `bytes` feature, a projection into a `'static` and thread-safe buffer converts into a
+ `bytes::Bytes` without copying, which keeps the projection (and so the buffer) alive until the
+ last clone of the `Bytes` is dropped.
+*/
+pub type SubarcBytes<T> = Subarc<T, [u8]>;
+
+/// Two [`Subrc`]s sharing the same owner, see [`SubptrPair`].
+pub type SubrcPair<T, U, V> = SubptrPair<Rc<T>, U, V>;
+
+/// Two [`Subarc`]s sharing the same owner, see [`SubptrPair`].
+pub type SubarcPair<T, U, V> = SubptrPair<Arc<T>, U, V>;
+
+/// A compact [`Subrc`], see [`SmallSubptr`].
+pub type SmallSubrc<T, U> = SmallSubptr<Rc<T>, U>;
+
+/// A compact [`Subarc`], see [`SmallSubptr`].
+pub type SmallSubarc<T, U> = SmallSubptr<Arc<T>, U>;
+
+/// A [`Subrc`] to a possibly misaligned value, see [`SubptrUnaligned`].
+pub type SubrcUnaligned<T, U> = SubptrUnaligned<Rc<T>, U>;
+
+/// A [`Subarc`] to a possibly misaligned value, see [`SubptrUnaligned`].
+pub type SubarcUnaligned<T, U> = SubptrUnaligned<Arc<T>, U>;
+
+/// A weak version of [`Subrc`], see [`WeakSubptr`].
+pub type SubWeak<T, U> = WeakSubptr<Rc<T>, U>;
+
+/// A weak version of [`Subarc`], see [`WeakSubptr`].
+pub type SubarcWeak<T, U> = WeakSubptr<Arc<T>, U>;
 
 /**
  Create a [`Subrc`] pointer, which points to a subregion of the specified [`Rc`].
  # Example
 ```rust
+# use std::rc::Rc;
+# use subrc::subrc;
 struct Foo {
     value: i32,
 }
@@ -104,18 +218,235 @@ let subrc = subrc!(rc.value);
 assert_eq!(*subrc, 42);
 // subrc points to rc.value
 assert!(std::ptr::eq(&*subrc, &rc.value));
+```
+
+ The owner can also be any expression, followed by `=>` and a path made of field accesses and
+ indexing, which is projected one step at a time with [`Subptr::map`]:
+```rust
+# use std::rc::Rc;
+# use subrc::subrc;
+struct Foo {
+    bars: [Bar; 3],
+}
+struct Bar {
+    value: i32,
+}
+let foo = (0, Rc::new(Foo { bars: [Bar { value: 1 }, Bar { value: 2 }, Bar { value: 3 }] }));
+let subrc = subrc!(foo.1 => .bars[2].value);
+assert_eq!(*subrc, 3);
+assert!(std::ptr::eq(&*subrc, &foo.1.bars[2].value));
 ```
  */
 #[macro_export]
 macro_rules! subrc {
     ($rc:ident$(.$field:ident)*) => {
-        Subrc::new($rc.clone(), |t| &t$(.$field)* )
+        $crate::Subptr::new($rc.clone(), |t| &t$(.$field)* )
+    };
+    ($rc:expr => . $field:tt $($path:tt)*) => {
+        $crate::__subrc_path!(
+            $crate::Subptr::new(::core::clone::Clone::clone(&$rc), |t| &t.$field);
+            $($path)*
+        )
+    };
+    ($rc:expr => [$index:expr] $($path:tt)*) => {
+        $crate::__subrc_path!(
+            $crate::Subptr::new(::core::clone::Clone::clone(&$rc), |t| &t[$index]);
+            $($path)*
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __subrc_path {
+    ($subptr:expr;) => {
+        $subptr
     };
+    ($subptr:expr; . $field:tt $($path:tt)*) => {
+        $crate::__subrc_path!($subptr.map(|t| &t.$field); $($path)*)
+    };
+    ($subptr:expr; [$index:expr] $($path:tt)*) => {
+        $crate::__subrc_path!($subptr.map(|t| &t[$index]); $($path)*)
+    };
+}
+
+/**
+ Create a [`Subarc`] pointer, which points to a subregion of the specified [`Arc`].
+ # Example
+```rust
+# use std::sync::Arc;
+# use subrc::subarc;
+struct Foo {
+    value: i32,
+}
+let arc = Arc::new(Foo { value: 42 });
+let subarc = subarc!(arc.value);
+// subarc derefs to 42
+assert_eq!(*subarc, 42);
+// subarc points to arc.value
+assert!(std::ptr::eq(&*subarc, &arc.value));
+```
+ */
+#[macro_export]
+macro_rules! subarc {
+    ($arc:ident$(.$field:ident)*) => {
+        $crate::Subptr::new($arc.clone(), |t| &t$(.$field)* )
+    };
+}
+
+/**
+ Create a [`WeakSubptr`] to a (possibly nested) field of the value the specified `&rc::Weak` or
+ `&sync::Weak` points to, with the field specified by its path within the owner type, like
+ [`subrc_field`] does for an owner.
+
+ As the offset of the field is computed at compile time, the value doesn't need to be initialized
+ yet, so this works within [`Rc::new_cyclic`], letting self-referential values store weak
+ projections to their own fields.
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{subweak_field, SubWeak};
+struct Node {
+    name: String,
+    this: SubWeak<Node, String>,
+}
+
+let node = Rc::new_cyclic(|weak| Node {
+    name: String::from("root"),
+    this: subweak_field!(weak, Node, name),
+});
+assert_eq!(*node.this.upgrade().unwrap(), "root");
+```
+ */
+#[macro_export]
+macro_rules! subweak_field {
+    ($weak:expr, $owner:ty, $($field:tt).+) => {{
+        let offset = ::core::mem::offset_of!($owner, $($field).+);
+        // SAFETY: see `weak_from_field_offset`.
+        #[allow(clippy::macro_metavars_in_unsafe)]
+        let weak = unsafe {
+            $crate::__private::weak_from_field_offset($weak, offset, |t: &$owner| &t.$($field).+)
+        };
+        weak
+    }};
+}
+
+/**
+ Create a [`Subptr`] pointer to a (possibly nested) field of the value owned by the specified
+ [`Rc`] or [`Arc`], with the field specified by its path within the owner type.
+
+ The offset of the field is computed at compile time with [`core::mem::offset_of`], so unlike
+ [`Subptr::new`], no validation happens at runtime and the constructor can never panic.
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::subrc_field;
+struct Foo {
+    bar: Bar,
+}
+struct Bar {
+    baz: i32,
+}
+let rc = Rc::new(Foo { bar: Bar { baz: 42 } });
+let subrc = subrc_field!(rc, Foo, bar.baz);
+assert_eq!(*subrc, 42);
+assert!(std::ptr::eq(&*subrc, &rc.bar.baz));
+```
+ */
+#[macro_export]
+macro_rules! subrc_field {
+    ($rc:expr, $owner:ty, $($field:tt).+) => {{
+        let owner = ::core::clone::Clone::clone(&$rc);
+        let offset = ::core::mem::offset_of!($owner, $($field).+);
+        // SAFETY: see `from_field_offset`.
+        #[allow(clippy::macro_metavars_in_unsafe)]
+        let subptr = unsafe {
+            $crate::Subptr::from_field_offset(owner, offset, |t: &$owner| &t.$($field).+)
+        };
+        subptr
+    }};
+}
+
+/**
+ Create a [`SubptrUnaligned`] to a (possibly nested, possibly misaligned) field of the value owned
+ by the specified [`Rc`] or [`Arc`], such as a field of a `#[repr(packed)]` struct, with the field
+ specified by its path within the owner type.
+
+ The path is checked with [`core::mem::offset_of`], which only accepts plain field accesses, so
+ the projection always reads a valid value of the field's type.
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::subrc_unaligned;
+#[repr(C, packed)]
+struct Header {
+    kind: u8,
+    length: u32,
+}
+let rc = Rc::new(Header { kind: 1, length: 512 });
+let length = subrc_unaligned!(rc, Header, length);
+assert_eq!(length.get(), 512);
+```
+ */
+#[macro_export]
+macro_rules! subrc_unaligned {
+    ($rc:expr, $owner:ty, $($field:tt).+) => {{
+        let owner = ::core::clone::Clone::clone(&$rc);
+        let _ = ::core::mem::offset_of!($owner, $($field).+);
+        // SAFETY: `offset_of` only accepts plain field accesses, so the getter returns a pointer to
+        // a field of the owned value, which holds a valid value of its type.
+        #[allow(clippy::macro_metavars_in_unsafe)]
+        let unaligned = unsafe {
+            $crate::SubptrUnaligned::new(owner, |t: &$owner| &raw const t.$($field).+)
+        };
+        unaligned
+    }};
+}
+
+/**
+ Create a [`SubrcCell`] projecting the value in the specified `Rc<Cell<T>>` to a (possibly nested)
+ field, with the field specified by its path within the value type.
+
+ The path is checked with [`core::mem::offset_of`], which only accepts plain field accesses.
+
+ # Example
+```rust
+# use std::{cell::Cell, rc::Rc};
+# use subrc::subrc_cell;
+#[derive(Clone, Copy)]
+struct Foo {
+    bar: Bar,
+}
+#[derive(Clone, Copy)]
+struct Bar {
+    baz: i32,
+}
+let rc = Rc::new(Cell::new(Foo { bar: Bar { baz: 42 } }));
+let baz = subrc_cell!(rc, Foo, bar.baz);
+baz.set(24);
+assert_eq!(rc.get().bar.baz, 24);
+```
+ */
+#[macro_export]
+macro_rules! subrc_cell {
+    ($rc:expr, $owner:ty, $($field:tt).+) => {{
+        let owner = ::core::clone::Clone::clone(&$rc);
+        let _ = ::core::mem::offset_of!($owner, $($field).+);
+        // SAFETY: `offset_of` only accepts plain field accesses, which the getter is made of.
+        #[allow(clippy::macro_metavars_in_unsafe)]
+        let cell = unsafe {
+            $crate::SubrcCell::new_unchecked(owner, |t: &$owner| &t.$($field).+)
+        };
+        cell
+    }};
 }
 
 #[cfg(test)]
 mod tests {
-    use std::rc::Rc;
+    use std::{rc::Rc, sync::Arc};
 
     use super::*;
 
@@ -152,4 +483,65 @@ mod tests {
         let rc = Rc::new(foo);
         let _subrc = Subrc::new(rc.clone(), |_| &42);
     }
+
+    #[test]
+    fn test_subarc_struct_member() {
+        let foo = Foo {
+            _value: 42,
+            bar: Bar { value: 24 },
+        };
+
+        let arc = Arc::new(foo);
+        let subarc = subarc!(arc.bar);
+        assert_eq!(subarc.value, 24);
+        assert!(std::ptr::eq(&*subarc, &arc.bar));
+    }
+
+    #[test]
+    fn test_subarc_across_threads() {
+        let arc = Arc::new((1u64, String::from("hello")));
+        let subarc = Subarc::new(arc.clone(), |t| &t.1);
+
+        let handle = std::thread::spawn(move || subarc.len());
+        assert_eq!(handle.join().unwrap(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn subarc_should_panic_for_invalid_offset() {
+        let arc = Arc::new(42u64);
+        let _subarc = Subarc::new(arc.clone(), |_| &42);
+    }
+
+    #[test]
+    fn test_subrc_field() {
+        let foo = Foo {
+            _value: 42,
+            bar: Bar { value: 24 },
+        };
+
+        let rc = Rc::new(foo);
+        let subrc = subrc_field!(rc, Foo, bar.value);
+        assert_eq!(*subrc, 24);
+        assert!(std::ptr::eq(&*subrc, &rc.bar.value));
+
+        let arc = Arc::new((1u8, 2u32));
+        let subarc: Subarc<_, u32> = subrc_field!(arc, (u8, u32), 1);
+        assert_eq!(*subarc, 2);
+    }
+
+    #[test]
+    fn test_subrc_path() {
+        let rc = Rc::new([(0u8, [Bar { value: 1 }, Bar { value: 2 }])]);
+        let subrc = subrc!(rc => [0].1[1].value);
+        assert_eq!(*subrc, 2);
+        assert!(std::ptr::eq(&*subrc, &rc[0].1[1].value));
+
+        let arc = Arc::new(Foo {
+            _value: 42,
+            bar: Bar { value: 24 },
+        });
+        let subarc = subrc!(arc => .bar.value);
+        assert!(std::ptr::eq(&*subarc, &arc.bar.value));
+    }
 }
@@ -0,0 +1,131 @@
+use alloc::collections::BTreeMap;
+use core::borrow::Borrow;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+/**
+* A map whose values can be looked up by a key of type `Q`, for
+* [`project_value`](Subptr::project_value).
+*
+* Implemented for [`BTreeMap`], and for `HashMap` with the `std` feature.
+*/
+pub trait Lookup<Q: ?Sized> {
+    /// The type of the values of the map.
+    type Value;
+
+    /// Get the value stored for `key`, if any.
+    fn lookup(&self, key: &Q) -> Option<&Self::Value>;
+}
+
+impl<K, V, Q> Lookup<Q> for BTreeMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: ?Sized + Ord,
+{
+    type Value = V;
+
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        self.get(key)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, Q, S> Lookup<Q> for HashMap<K, V, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: ?Sized + Hash + Eq,
+    S: BuildHasher,
+{
+    type Value = V;
+
+    fn lookup(&self, key: &Q) -> Option<&V> {
+        self.get(key)
+    }
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner,
+    U: Pointee,
+{
+    /**
+       Create a [`Subptr`] to the value stored for `key` in the map returned by `getter`, or
+       [`None`] if there is none.
+
+       The values of a map live outside of the owned value, like those reached through
+       [`new_indirect`](Subptr::new_indirect), and stay in place while it is shared.
+
+       ## Example
+       ```rust
+           # use std::{collections::BTreeMap, rc::Rc};
+           # use subrc::Subrc;
+           struct Registry {
+               services: BTreeMap<String, u16>,
+           }
+
+           let services = BTreeMap::from([(String::from("http"), 80)]);
+           let rc = Rc::new(Registry { services });
+           let http = Subrc::project_value(&rc, |r| &r.services, "http").unwrap();
+           assert!(std::ptr::eq(&*http, &rc.services["http"]));
+           assert!(Subrc::project_value(&rc, |r| &r.services, "ftp").is_none());
+       ```
+    */
+    pub fn project_value<M, Q, F>(owner: &P, getter: F, key: &Q) -> Option<Self>
+    where
+        M: Lookup<Q, Value = U>,
+        Q: ?Sized,
+        F: FnOnce(&P::Target) -> &M,
+    {
+        let ptr = getter(owner).lookup(key)? as *const U;
+        // SAFETY: the value was borrowed from the owned value, which the clone of `owner` keeps
+        // alive and shared, see `new_indirect`.
+        Some(unsafe { Self::from_raw_parts(owner.clone(), ptr) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc};
+    #[cfg(feature = "std")]
+    use std::{collections::HashMap, rc::Rc};
+
+    use crate::Subarc;
+    #[cfg(feature = "std")]
+    use crate::Subrc;
+
+    struct Index {
+        #[cfg(feature = "std")]
+        by_name: HashMap<String, Vec<u32>>,
+        by_id: BTreeMap<u32, String>,
+    }
+
+    fn index() -> Index {
+        Index {
+            #[cfg(feature = "std")]
+            by_name: HashMap::from([(String::from("a"), vec![1, 2]), (String::from("b"), vec![])]),
+            by_id: BTreeMap::from([(1, String::from("a")), (2, String::from("a"))]),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_project_value() {
+        let rc = Rc::new(index());
+        let a = Subrc::project_value(&rc, |index| &index.by_name, "a").unwrap();
+        assert!(std::ptr::eq(&*a, &rc.by_name["a"]));
+        assert!(Subrc::project_value(&rc, |index| &index.by_name, "c").is_none());
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+
+    #[test]
+    fn test_project_btree_value() {
+        let arc = Arc::new(index());
+        let name = Subarc::project_value(&arc, |index| &index.by_id, &2).unwrap();
+        assert!(std::ptr::eq(&*name, &arc.by_id[&2]));
+        assert!(Subarc::project_value(&arc, |index| &index.by_id, &3).is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use core::cell::{LazyCell, OnceCell};
+#[cfg(feature = "std")]
+use std::sync::{LazyLock, OnceLock};
+
+use crate::{SharedOwner, Subptr};
+
+macro_rules! impl_once {
+    ($once:ident, $example_owner:literal, $example_use:literal) => {
+        impl<P, V> Subptr<P, $once<V>>
+        where
+            P: SharedOwner,
+        {
+            #[doc = concat!("
+               Return a projection to the value of the projected [`", stringify!($once), "`], or
+               [`None`] if it isn't initialized yet.
+
+               ## Example
+               ```rust
+                   # use ", $example_use, ";
+                   # use subrc::Subptr;
+                   struct Foo {
+                       cache: ", stringify!($once), "<String>,
+                   }
+
+                   let owner = ", $example_owner, "::new(Foo { cache: ", stringify!($once), "::new() });
+                   let cache = Subptr::new(owner.clone(), |foo| &foo.cache);
+                   assert!(cache.try_project_init().is_none());
+
+                   owner.cache.set(String::from(\"cached\")).unwrap();
+                   let value = cache.try_project_init().unwrap();
+                   assert!(std::ptr::eq(&*value, owner.cache.get().unwrap()));
+               ```
+            ")]
+            pub fn try_project_init(&self) -> Option<Subptr<P, V>> {
+                let value = self.get().get()?;
+                // SAFETY: the value lies within the cell, which the owner keeps alive. It can't be
+                // taken out of the cell without unique access to it.
+                Some(unsafe { Subptr::from_raw_parts(self.owner().clone(), value) })
+            }
+        }
+    };
+}
+
+impl_once!(OnceCell, "Rc", "std::{cell::OnceCell, rc::Rc}");
+#[cfg(feature = "std")]
+impl_once!(OnceLock, "Arc", "std::sync::{Arc, OnceLock}");
+
+macro_rules! impl_lazy {
+    ($lazy:ident, $example_owner:literal, $example_use:literal) => {
+        impl<P, V, F> Subptr<P, $lazy<V, F>>
+        where
+            P: SharedOwner,
+            F: FnOnce() -> V,
+        {
+            #[doc = concat!("
+               Return a projection to the value of the projected [`", stringify!($lazy), "`],
+               forcing its initialization first.
+
+               ## Example
+               ```rust
+                   # use ", $example_use, ";
+                   # use subrc::Subptr;
+                   struct Foo {
+                       lazy: ", stringify!($lazy), "<u32>,
+                   }
+
+                   let owner = ", $example_owner, "::new(Foo { lazy: ", stringify!($lazy), "::new(|| 42) });
+                   let value = Subptr::new(owner, |foo| &foo.lazy).project_force();
+                   assert_eq!(*value, 42);
+               ```
+            ")]
+            pub fn project_force(&self) -> Subptr<P, V> {
+                let value = $lazy::force(self.get());
+                // SAFETY: see `try_project_init`.
+                unsafe { Subptr::from_raw_parts(self.owner().clone(), value) }
+            }
+        }
+    };
+}
+
+impl_lazy!(LazyCell, "Rc", "std::{cell::LazyCell, rc::Rc}");
+#[cfg(feature = "std")]
+impl_lazy!(LazyLock, "Arc", "std::sync::{Arc, LazyLock}");
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::{LazyCell, OnceCell},
+        rc::Rc,
+    };
+
+    use crate::Subrc;
+
+    struct Foo {
+        once: OnceCell<[u8; 4]>,
+        lazy: LazyCell<String>,
+    }
+
+    #[test]
+    fn test_try_project_init() {
+        let rc = Rc::new(Foo {
+            once: OnceCell::new(),
+            lazy: LazyCell::new(|| String::from("lazy")),
+        });
+        let once = Subrc::new(rc.clone(), |foo| &foo.once);
+        assert!(once.try_project_init().is_none());
+
+        rc.once.get_or_init(|| [1, 2, 3, 4]);
+        let value = once.try_project_init().unwrap().map(|value| &value[2]);
+        assert!(std::ptr::eq(&*value, &rc.once.get().unwrap()[2]));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_try_project_init_lock() {
+        use std::sync::{Arc, OnceLock};
+
+        use crate::Subarc;
+
+        let arc = Arc::new((0u8, OnceLock::new()));
+        let once = Subarc::new(arc.clone(), |t| &t.1);
+        arc.1.set(42u64).unwrap();
+        let value = std::thread::spawn(move || *once.try_project_init().unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn test_project_force() {
+        let rc = Rc::new(Foo {
+            once: OnceCell::new(),
+            lazy: LazyCell::new(|| String::from("lazy")),
+        });
+        let lazy = Subrc::new(rc.clone(), |foo| &foo.lazy).project_force();
+        assert_eq!(*lazy, "lazy");
+        assert!(std::ptr::eq(&*lazy, &*rc.lazy));
+    }
+}
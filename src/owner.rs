@@ -0,0 +1,308 @@
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::Allocator;
+use alloc::{
+    rc::{Rc, Weak as RcWeak},
+    sync::{Arc, Weak as ArcWeak},
+};
+use core::ops::Deref;
+
+/**
+ A reference counted smart pointer which can act as the owner of a [`Subptr`](crate::Subptr).
+
+ The owner keeps the pointee alive, and the pointee must stay at the same address for as long as
+ any clone of the owner exists, which is what allows a [`Subptr`](crate::Subptr) to remember its
+ subregion as a plain pointer.
+
+ It is implemented for [`Rc`] and [`Arc`]. With the `allocator_api` feature, it is implemented for
+ them in any [`Clone`] allocator, e.g. for `Rc<T, MyArena>`. With the `triomphe` feature, it is
+ also implemented for `triomphe::Arc`. Other reference counted pointers can implement it too, so
+ their users get projections for free.
+
+ # Safety
+ - All the clones of an owner must deref to the same value, at the same address, which must stay
+   alive and in place for as long as any of them exists. This holds for an owner returned by
+   [`WeakOwner::upgrade`] as well.
+ - The owned value must not be mutated through a shared reference (other than through an
+   [`UnsafeCell`](core::cell::UnsafeCell)) while it is shared.
+ - [`get_mut`](SharedOwner::get_mut) and [`try_unwrap`](SharedOwner::try_unwrap) must only
+   succeed if there is no other owner of the value, nor any weak pointer to it, except for weak
+   pointers which can't be upgraded anymore in the case of `try_unwrap`.
+   [`make_mut`](SharedOwner::make_mut) must only return a reference to a value which isn't shared,
+   and from then on, `this` must deref to that value.
+ - The references returned by `get_mut` and `make_mut` must point to the value `this` derefs to.
+
+ The counts are only informational, and are not relied upon for safety.
+
+ # Example
+```rust
+# use std::{ops::Deref, rc::Rc};
+# use subrc::{SharedOwner, Subptr};
+struct Shared<T>(Rc<T>);
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Shared(self.0.clone())
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+// SAFETY: all the methods forward to `Rc`.
+unsafe impl<T> SharedOwner for Shared<T> {
+    fn strong_count(this: &Self) -> usize {
+        Rc::strong_count(&this.0)
+    }
+
+    fn weak_count(this: &Self) -> usize {
+        Rc::weak_count(&this.0)
+    }
+
+    fn try_unwrap(this: Self) -> Result<T, Self> {
+        Rc::try_unwrap(this.0).map_err(Shared)
+    }
+
+    fn get_mut(this: &mut Self) -> Option<&mut T> {
+        Rc::get_mut(&mut this.0)
+    }
+
+    fn make_mut(this: &mut Self) -> &mut T
+    where
+        T: Clone,
+    {
+        Rc::make_mut(&mut this.0)
+    }
+}
+
+let shared = Shared(Rc::new((1u8, 2u32)));
+let subptr = Subptr::new(shared.clone(), |t| &t.1);
+assert!(std::ptr::eq(&*subptr, &shared.1));
+```
+*/
+pub unsafe trait SharedOwner: Clone + Deref {
+    /// Get the number of owners of the owned value, see [`Rc::strong_count`]. This is at least one,
+    /// since `this` is an owner, and exactly one for owners which don't count their owners, such
+    /// as a garbage collected pointer, which must then override [`is_unique`](Self::is_unique).
+    fn strong_count(this: &Self) -> usize;
+
+    /// Get the number of weak pointers to the owned value, see [`Rc::weak_count`]. This is always
+    /// zero for owners which don't implement [`WeakOwner`].
+    fn weak_count(this: &Self) -> usize;
+
+    /// Return whether this is the only owner of the owned value, and there are no weak pointers to
+    /// it either. Defaults to checking both counts.
+    fn is_unique(this: &Self) -> bool {
+        Self::strong_count(this) == 1 && Self::weak_count(this) == 0
+    }
+
+    /// Return the owned value if this is its only owner, or the owner back otherwise, see
+    /// [`Rc::try_unwrap`].
+    fn try_unwrap(this: Self) -> Result<Self::Target, Self>
+    where
+        Self::Target: Sized;
+
+    /// Get a mutable reference to the owned value if this is its only owner, see [`Rc::get_mut`].
+    fn get_mut(this: &mut Self) -> Option<&mut Self::Target>;
+
+    /// Get a mutable reference to the owned value, cloning it first if it is shared, see
+    /// [`Rc::make_mut`].
+    fn make_mut(this: &mut Self) -> &mut Self::Target
+    where
+        Self::Target: Clone;
+
+    /// Get a mutable reference to the owned value without checking that it isn't shared, see
+    /// [`Rc::get_mut_unchecked`].
+    ///
+    /// The default implementation panics unless this is the only owner, like
+    /// [`get_mut`](SharedOwner::get_mut) failing would.
+    ///
+    /// # Safety
+    /// See [`Rc::get_mut_unchecked`].
+    #[cfg(feature = "nightly")]
+    unsafe fn get_mut_unchecked(this: &mut Self) -> &mut Self::Target {
+        Self::get_mut(this).expect("the owned value is shared")
+    }
+}
+
+/**
+ A [`SharedOwner`] which also has non-owning weak pointers, allowing its projections to be
+ downgraded to a [`WeakSubptr`](crate::WeakSubptr).
+
+ It is implemented for [`Rc`] and [`Arc`].
+
+ # Safety
+ [`upgrade`](WeakOwner::upgrade) must only return an owner of the very value the weak pointer was
+ [`downgrade`](WeakOwner::downgrade)d from, and only while that value is alive.
+*/
+pub unsafe trait WeakOwner: SharedOwner {
+    /// The non-owning counterpart of this owner, e.g. [`rc::Weak`](RcWeak) for [`Rc`].
+    type Weak: Clone;
+
+    /// Create a weak pointer to the owned value, see [`Rc::downgrade`].
+    fn downgrade(this: &Self) -> Self::Weak;
+
+    /// Attempt to recover the owner from a weak pointer, see [`rc::Weak::upgrade`](RcWeak::upgrade).
+    fn upgrade(weak: &Self::Weak) -> Option<Self>;
+}
+
+macro_rules! impl_shared_owner {
+    ($owner:ident, $weak:ident) => {
+        #[cfg(not(feature = "allocator_api"))]
+        impl_shared_owner!($owner<T>, $weak<T>, [T: ?Sized]);
+        #[cfg(feature = "allocator_api")]
+        impl_shared_owner!($owner<T, A>, $weak<T, A>, [T: ?Sized, A: Allocator + Clone]);
+    };
+    ($owner:ident<$($param:ident),+>, $weak:ty, [$($generics:tt)*]) => {
+        // SAFETY: `Rc` and `Arc` uphold these guarantees, as they are those of their own API.
+        unsafe impl<$($generics)*> SharedOwner for $owner<$($param),+> {
+            fn strong_count(this: &Self) -> usize {
+                $owner::strong_count(this)
+            }
+
+            fn weak_count(this: &Self) -> usize {
+                $owner::weak_count(this)
+            }
+
+            fn try_unwrap(this: Self) -> Result<Self::Target, Self>
+            where
+                Self::Target: Sized,
+            {
+                $owner::try_unwrap(this)
+            }
+
+            fn get_mut(this: &mut Self) -> Option<&mut Self::Target> {
+                $owner::get_mut(this)
+            }
+
+            fn make_mut(this: &mut Self) -> &mut Self::Target
+            where
+                Self::Target: Clone,
+            {
+                $owner::make_mut(this)
+            }
+
+            #[cfg(feature = "nightly")]
+            unsafe fn get_mut_unchecked(this: &mut Self) -> &mut Self::Target {
+                $owner::get_mut_unchecked(this)
+            }
+        }
+
+        unsafe impl<$($generics)*> WeakOwner for $owner<$($param),+> {
+            type Weak = $weak;
+
+            fn downgrade(this: &Self) -> Self::Weak {
+                $owner::downgrade(this)
+            }
+
+            fn upgrade(weak: &Self::Weak) -> Option<Self> {
+                weak.upgrade()
+            }
+        }
+    };
+}
+
+impl_shared_owner!(Rc, RcWeak);
+impl_shared_owner!(Arc, ArcWeak);
+
+// SAFETY: `triomphe::Arc` behaves like `Arc`, without weak pointers.
+#[cfg(feature = "triomphe")]
+unsafe impl<T: ?Sized> SharedOwner for ::triomphe::Arc<T> {
+    fn strong_count(this: &Self) -> usize {
+        ::triomphe::Arc::count(this)
+    }
+
+    fn weak_count(_this: &Self) -> usize {
+        0
+    }
+
+    fn try_unwrap(this: Self) -> Result<Self::Target, Self>
+    where
+        Self::Target: Sized,
+    {
+        ::triomphe::Arc::try_unwrap(this)
+    }
+
+    fn get_mut(this: &mut Self) -> Option<&mut Self::Target> {
+        ::triomphe::Arc::get_mut(this)
+    }
+
+    fn make_mut(this: &mut Self) -> &mut Self::Target
+    where
+        Self::Target: Clone,
+    {
+        ::triomphe::Arc::make_mut(this)
+    }
+
+    #[cfg(feature = "nightly")]
+    unsafe fn get_mut_unchecked(this: &mut Self) -> &mut Self::Target {
+        &mut *(::triomphe::Arc::as_ptr(this) as *mut Self::Target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(feature = "triomphe")]
+    fn test_triomphe_owner() {
+        use triomphe::Arc;
+
+        use crate::Subptr;
+
+        let arc = Arc::new((1u8, [2u16, 3]));
+        let mut items = Subptr::new(arc.clone(), |t| &t.1);
+        assert!(std::ptr::eq(&*items, &arc.1));
+        assert_eq!(Subptr::strong_count(&items), 2);
+        assert_eq!(Subptr::get_mut(&mut items), None);
+
+        Subptr::make_mut(&mut items)[0] = 4;
+        assert_eq!(*items, [4, 3]);
+        assert_eq!(arc.1, [2, 3]);
+        assert!(Subptr::is_unique(&items));
+        assert_eq!(Subptr::try_unwrap_owner(items).ok(), Some((1, [4, 3])));
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn test_allocator_owner() {
+        use std::{
+            alloc::{AllocError, Allocator, Global, Layout},
+            cell::Cell,
+            ptr::NonNull,
+            rc::Rc,
+        };
+
+        use crate::Subptr;
+
+        #[derive(Clone)]
+        struct Counting(Rc<Cell<usize>>);
+
+        unsafe impl Allocator for Counting {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.0.set(self.0.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                Global.deallocate(ptr, layout)
+            }
+        }
+
+        let count = Rc::new(Cell::new(0));
+        let rc = Rc::new_in((1u8, [2u16, 3]), Counting(count.clone()));
+        let mut items: Subptr<Rc<_, Counting>, [u16; 2]> = Subptr::new(rc.clone(), |t| &t.1);
+        assert_eq!(count.get(), 1);
+
+        let weak = Subptr::downgrade(&items);
+        assert!(std::ptr::eq(&*weak.upgrade().unwrap(), &rc.1));
+
+        Subptr::make_mut(&mut items)[0] = 4;
+        assert_eq!(count.get(), 2);
+        assert_eq!(*items, [4, 3]);
+        assert_eq!(rc.1, [2, 3]);
+    }
+}
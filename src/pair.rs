@@ -0,0 +1,174 @@
+use core::{marker::PhantomData, ptr};
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+/**
+* Two projections into the same owned value, sharing a single reference to the owner.
+*
+* It is created by [`Subptr::zip`], and exposes both targets together with [`get`](SubptrPair::get).
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{subrc, SubrcPair};
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+let rc = Rc::new(Point { x: 1, y: 2 });
+let pair: SubrcPair<Point, i32, i32> = subrc!(rc.x).zip(subrc!(rc.y));
+assert_eq!(pair.get(), (&1, &2));
+assert_eq!(Rc::strong_count(&rc), 2);
+```
+*/
+pub struct SubptrPair<P, U: ?Sized + Pointee, V: ?Sized + Pointee> {
+    first: Subptr<P, U>,
+    second: *const V,
+    // Invariant in `V` like `Subptr`, which it unzips into.
+    _second: PhantomData<fn(V) -> V>,
+}
+
+// SAFETY: like a `Subptr`, a pair behaves like its owner bundled with shared references to its
+// targets.
+unsafe impl<P, U, V> Send for SubptrPair<P, U, V>
+where
+    P: Send,
+    U: ?Sized + Pointee + Sync,
+    V: ?Sized + Pointee + Sync,
+{
+}
+unsafe impl<P, U, V> Sync for SubptrPair<P, U, V>
+where
+    P: Sync,
+    U: ?Sized + Pointee + Sync,
+    V: ?Sized + Pointee + Sync,
+{
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    /**
+       Pair this projection with another projection into the same owned value, keeping only one
+       reference to the owner.
+
+       # Panics
+       Panics if the two projections don't share the same owner. See
+       [`try_zip`](Subptr::try_zip) for a non-panicking version.
+    */
+    pub fn zip<V>(self, other: Subptr<P, V>) -> SubptrPair<P, U, V>
+    where
+        V: ?Sized + Pointee,
+    {
+        match self.try_zip(other) {
+            Ok(pair) => pair,
+            Err(_) => panic!("the projections don't share the same owner"),
+        }
+    }
+
+    /**
+       Like [`zip`](Subptr::zip), but gives the two projections back if they don't share the same
+       owner.
+    */
+    #[allow(clippy::type_complexity)]
+    pub fn try_zip<V>(
+        self,
+        other: Subptr<P, V>,
+    ) -> Result<SubptrPair<P, U, V>, (Self, Subptr<P, V>)>
+    where
+        V: ?Sized + Pointee,
+    {
+        if !ptr::addr_eq(&**self.owner(), &**other.owner()) {
+            return Err((self, other));
+        }
+        let (_, second) = Subptr::into_raw_parts(other);
+        Ok(SubptrPair {
+            first: self,
+            second,
+            _second: PhantomData,
+        })
+    }
+}
+
+impl<P, U, V> SubptrPair<P, U, V>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+    V: ?Sized + Pointee,
+{
+    /// Get references to both targets.
+    pub fn get(&self) -> (&U, &V) {
+        (self.first(), self.second())
+    }
+
+    /// Get a reference to the first target.
+    pub fn first(&self) -> &U {
+        self.first.get()
+    }
+
+    /// Get a reference to the second target.
+    pub fn second(&self) -> &V {
+        // SAFETY: the pointer is valid for this very owner, since the `Subptr` we were zipped from
+        // shared it.
+        unsafe { &*self.second }
+    }
+
+    /// Split the pair back into two separate projections.
+    pub fn unzip(self) -> (Subptr<P, U>, Subptr<P, V>) {
+        // SAFETY: see `second`.
+        let second = unsafe { Subptr::from_raw_parts(self.first.owner().clone(), self.second) };
+        (self.first, second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::Subrc;
+
+    struct Foo {
+        a: u32,
+        b: [u8; 4],
+    }
+
+    fn foo() -> Rc<Foo> {
+        Rc::new(Foo {
+            a: 1,
+            b: [2, 3, 4, 5],
+        })
+    }
+
+    #[test]
+    fn test_zip_unzip() {
+        let rc = foo();
+        let a = Subrc::new(rc.clone(), |foo| &foo.a);
+        let b = Subrc::<_, [u8]>::new(rc.clone(), |foo| &foo.b[1..]);
+        let pair = a.zip(b);
+        assert_eq!(pair.get(), (&1, &[3, 4, 5][..]));
+        assert_eq!(Rc::strong_count(&rc), 2);
+
+        let (a, b) = pair.unzip();
+        assert!(std::ptr::eq(&*a, &rc.a));
+        assert!(std::ptr::eq(&*b, &rc.b[1..]));
+    }
+
+    #[test]
+    fn test_try_zip_different_owners() {
+        let (rc1, rc2) = (foo(), foo());
+        let a = Subrc::new(rc1.clone(), |foo| &foo.a);
+        let b = Subrc::new(rc2.clone(), |foo| &foo.b);
+        let (a, b) = a.try_zip(b).err().unwrap();
+        assert!(std::ptr::eq(&*a, &rc1.a));
+        assert!(std::ptr::eq(&*b, &rc2.b));
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_for_zip_different_owners() {
+        let _pair = Subrc::new(foo(), |foo| &foo.a).zip(Subrc::new(foo(), |foo| &foo.b));
+    }
+}
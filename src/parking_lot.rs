@@ -0,0 +1,220 @@
+/*!
+ Projections to subregions of values behind [`parking_lot`] locks.
+
+ These are the [`parking_lot`] counterparts of [`SubMutex`](crate::SubMutex) and
+ [`SubRwLock`](crate::SubRwLock). Their guards are the mapped guards of [`parking_lot`], which
+ only hold a pointer to the projected subregion.
+*/
+
+use std::sync::Arc;
+
+use ::parking_lot::{
+    MappedMutexGuard, MappedRwLockReadGuard, MappedRwLockWriteGuard, Mutex, MutexGuard, RwLock,
+    RwLockReadGuard, RwLockWriteGuard,
+};
+
+/**
+* A projection to a subregion of the value behind an `Arc<parking_lot::Mutex<T>>`.
+*
+* # Example
+```rust
+# use std::sync::Arc;
+# use parking_lot::Mutex;
+# use subrc::parking_lot::SubMutex;
+struct State {
+    name: String,
+    count: u32,
+}
+
+let arc = Arc::new(Mutex::new(State { name: String::from("state"), count: 0 }));
+let count = SubMutex::new(arc.clone(), |state| &mut state.count);
+std::thread::spawn(move || *count.lock() += 1).join().unwrap();
+assert_eq!(arc.lock().count, 1);
+```
+*/
+pub struct SubMutex<T: ?Sized, U: ?Sized> {
+    owner: Arc<Mutex<T>>,
+    getter: fn(&mut T) -> &mut U,
+}
+
+impl<T, U> SubMutex<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /**
+       Create a [`SubMutex`] projecting the value behind `owner` to the subregion returned by
+       `getter`, which is called every time the mutex is locked.
+    */
+    pub fn new(owner: Arc<Mutex<T>>, getter: fn(&mut T) -> &mut U) -> Self {
+        SubMutex { owner, getter }
+    }
+
+    /// Lock the mutex, returning a guard to the projected subregion, see [`Mutex::lock`].
+    pub fn lock(&self) -> MappedMutexGuard<'_, U> {
+        MutexGuard::map(self.owner.lock(), self.getter)
+    }
+
+    /// Attempt to lock the mutex without blocking, see [`Mutex::try_lock`].
+    pub fn try_lock(&self) -> Option<MappedMutexGuard<'_, U>> {
+        Some(MutexGuard::map(self.owner.try_lock()?, self.getter))
+    }
+}
+
+impl<T, U> Clone for SubMutex<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    fn clone(&self) -> Self {
+        SubMutex {
+            owner: self.owner.clone(),
+            getter: self.getter,
+        }
+    }
+}
+
+/**
+* A projection to a subregion of the value behind an `Arc<parking_lot::RwLock<T>>`.
+*
+* # Example
+```rust
+# use std::sync::Arc;
+# use parking_lot::RwLock;
+# use subrc::parking_lot::SubRwLock;
+struct State {
+    name: String,
+    count: u32,
+}
+
+let arc = Arc::new(RwLock::new(State { name: String::from("state"), count: 0 }));
+let name = SubRwLock::new(arc.clone(), |state| &state.name, |state| &mut state.name);
+name.write().push('!');
+assert_eq!(*name.read(), "state!");
+```
+*/
+pub struct SubRwLock<T: ?Sized, U: ?Sized> {
+    owner: Arc<RwLock<T>>,
+    getter: fn(&T) -> &U,
+    getter_mut: fn(&mut T) -> &mut U,
+}
+
+impl<T, U> SubRwLock<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /**
+       Create a [`SubRwLock`] projecting the value behind `owner` to the subregion returned by
+       `getter` when reading, and by `getter_mut` when writing. They should return the same
+       subregion.
+    */
+    pub fn new(
+        owner: Arc<RwLock<T>>,
+        getter: fn(&T) -> &U,
+        getter_mut: fn(&mut T) -> &mut U,
+    ) -> Self {
+        SubRwLock {
+            owner,
+            getter,
+            getter_mut,
+        }
+    }
+
+    /**
+       Lock the value for reading, returning a guard to the projected subregion, see
+       [`RwLock::read`].
+    */
+    pub fn read(&self) -> MappedRwLockReadGuard<'_, U> {
+        RwLockReadGuard::map(self.owner.read(), self.getter)
+    }
+
+    /// Attempt to lock the value for reading without blocking, see [`RwLock::try_read`].
+    pub fn try_read(&self) -> Option<MappedRwLockReadGuard<'_, U>> {
+        Some(RwLockReadGuard::map(self.owner.try_read()?, self.getter))
+    }
+
+    /**
+       Lock the value for writing, returning a guard to the projected subregion, see
+       [`RwLock::write`].
+    */
+    pub fn write(&self) -> MappedRwLockWriteGuard<'_, U> {
+        RwLockWriteGuard::map(self.owner.write(), self.getter_mut)
+    }
+
+    /// Attempt to lock the value for writing without blocking, see [`RwLock::try_write`].
+    pub fn try_write(&self) -> Option<MappedRwLockWriteGuard<'_, U>> {
+        Some(RwLockWriteGuard::map(
+            self.owner.try_write()?,
+            self.getter_mut,
+        ))
+    }
+}
+
+impl<T, U> Clone for SubRwLock<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    fn clone(&self) -> Self {
+        SubRwLock {
+            owner: self.owner.clone(),
+            getter: self.getter,
+            getter_mut: self.getter_mut,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ::parking_lot::{Mutex, RwLock};
+
+    use super::{SubMutex, SubRwLock};
+
+    struct State {
+        items: Vec<u32>,
+        name: String,
+    }
+
+    fn state() -> State {
+        State {
+            items: vec![1, 2, 3],
+            name: String::from("state"),
+        }
+    }
+
+    #[test]
+    fn test_sub_mutex() {
+        let arc = Arc::new(Mutex::new(state()));
+        let items = SubMutex::new(arc.clone(), |state| &mut state.items);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let items = items.clone();
+                std::thread::spawn(move || items.lock().push(4))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(arc.lock().items.len(), 7);
+
+        let guard = items.lock();
+        assert!(arc.try_lock().is_none());
+        assert!(items.try_lock().is_none());
+        drop(guard);
+    }
+
+    #[test]
+    fn test_sub_rw_lock() {
+        let arc = Arc::new(RwLock::new(state()));
+        let name = SubRwLock::new(arc.clone(), |state| &state.name, |state| &mut state.name);
+        name.write().push('!');
+
+        let (a, b) = (name.read(), name.try_read().unwrap());
+        assert_eq!(*a, "state!");
+        assert_eq!(*b, "state!");
+        assert!(name.try_write().is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use core::pin::Pin;
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+impl<P> Subptr<P, P::Target>
+where
+    P: SharedOwner,
+    P::Target: Pointee,
+{
+    /**
+       Create a pinned [`Subptr`] pointing to the whole value owned by the pinned `owner`, see
+       [`whole`](Subptr::whole).
+
+       ## Example
+       ```rust
+           # use std::{marker::PhantomPinned, rc::Rc};
+           # use subrc::Subrc;
+           struct Future {
+               state: u8,
+               _pinned: PhantomPinned,
+           }
+
+           let whole = Subrc::whole_pin(Rc::pin(Future { state: 1, _pinned: PhantomPinned }));
+           assert_eq!(whole.state, 1);
+       ```
+    */
+    pub fn whole_pin(owner: Pin<P>) -> Pin<Self> {
+        // SAFETY: the owned value stays behind its owner, and the target is the owned value itself,
+        // so it is pinned as well.
+        unsafe { Pin::new_unchecked(Self::whole(Pin::into_inner_unchecked(owner))) }
+    }
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    /**
+       Create a pinned [`Subptr`] from the pinned `owner`, pointing to a structurally pinned
+       subregion of the owned value, like [`Pin::map_unchecked`] does for a reference.
+
+       # Panics
+       Like [`new`](Subptr::new), the getter must return a subregion of the owned value.
+
+       # Safety
+       The target must be structurally pinned in the owned value, see
+       [pinning](core::pin#projections-and-structural-pinning). Moreover, if `U` is [`Unpin`], so
+       must be the owned value, since the owner can then be taken back out of the [`Pin`].
+
+       ## Example
+       ```rust
+           # use std::{marker::PhantomPinned, pin::Pin, rc::Rc};
+           # use subrc::Subrc;
+           struct State {
+               step: u8,
+               _pinned: PhantomPinned,
+           }
+
+           struct Future {
+               state: State,
+           }
+
+           let owner = Rc::pin(Future { state: State { step: 1, _pinned: PhantomPinned } });
+           let state: Pin<Subrc<Future, State>> =
+               unsafe { Subrc::new_pin_unchecked(owner, |future| &future.state) };
+           assert_eq!(state.step, 1);
+       ```
+    */
+    pub unsafe fn new_pin_unchecked<F>(owner: Pin<P>, getter: F) -> Pin<Self>
+    where
+        F: FnOnce(&P::Target) -> &U,
+    {
+        Pin::new_unchecked(Self::new(Pin::into_inner_unchecked(owner), getter))
+    }
+
+    /**
+       Project this pinned [`Subptr`] further down, to a structurally pinned subregion of its
+       current target, like [`Pin::map_unchecked`] does for a reference.
+
+       # Panics
+       Like [`map`](Subptr::map).
+
+       # Safety
+       See [`new_pin_unchecked`](Subptr::new_pin_unchecked).
+    */
+    pub unsafe fn map_pin_unchecked<V, F>(this: Pin<Self>, f: F) -> Pin<Subptr<P, V>>
+    where
+        V: ?Sized + Pointee,
+        F: FnOnce(&U) -> &V,
+    {
+        Pin::new_unchecked(Pin::into_inner_unchecked(this).map(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{marker::PhantomPinned, pin::Pin, rc::Rc, sync::Arc};
+
+    use crate::{Subarc, Subrc};
+
+    struct State {
+        step: u8,
+        _pinned: PhantomPinned,
+    }
+
+    struct Future {
+        _id: u32,
+        state: State,
+    }
+
+    fn future() -> Future {
+        Future {
+            _id: 7,
+            state: State {
+                step: 1,
+                _pinned: PhantomPinned,
+            },
+        }
+    }
+
+    #[test]
+    fn test_pin_projections() {
+        let owner = Rc::pin(future());
+        let whole = Subrc::whole_pin(owner.clone());
+        assert!(std::ptr::eq(&*whole, &*owner));
+
+        let state = unsafe { Subrc::map_pin_unchecked(whole, |future| &future.state) };
+        assert!(std::ptr::eq(&*state, &owner.state));
+        assert_eq!(state.step, 1);
+    }
+
+    #[test]
+    fn test_new_pin_unchecked() {
+        let owner = Arc::pin(future());
+        let state: Pin<Subarc<Future, State>> =
+            unsafe { Subarc::new_pin_unchecked(owner.clone(), |future| &future.state) };
+        assert!(std::ptr::eq(&*state, &owner.state));
+    }
+}
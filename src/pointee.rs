@@ -0,0 +1,154 @@
+use core::{any::Any, mem, ptr};
+
+/**
+ A type which can be the target of a [`Subptr`](crate::Subptr).
+
+ Pointers to unsized types carry metadata (e.g. the length of a slice) in addition to their
+ address. This trait gives access to that metadata, so that a pointer to a target can be rebuilt
+ from its address alone, e.g. at the same offset within another value.
+
+ This is a stable stand-in for the unstable `core::ptr::Pointee`, and is implemented for all
+ [`Sized`] types, slices, [`str`] and the [`Any`] trait objects. Other trait objects can opt in
+ with the [`impl_dyn_pointee`](crate::impl_dyn_pointee) macro.
+
+ # Safety
+ [`from_raw_parts`](Pointee::from_raw_parts), given the address of a value and the metadata
+ returned by [`metadata`](Pointee::metadata) for it, must return a pointer to that very value.
+*/
+pub unsafe trait Pointee {
+    /// The metadata stored alongside the address, e.g. `()` for sized types or the length of a slice.
+    type Metadata: Copy;
+
+    /// Get the metadata of a pointer to `self`.
+    fn metadata(&self) -> Self::Metadata;
+
+    /// Rebuild a (possibly wide) pointer from an address and the metadata.
+    fn from_raw_parts(data: *const u8, metadata: Self::Metadata) -> *const Self;
+}
+
+unsafe impl<T> Pointee for T {
+    type Metadata = ();
+
+    fn metadata(&self) -> Self::Metadata {}
+
+    fn from_raw_parts(data: *const u8, _metadata: Self::Metadata) -> *const Self {
+        data as *const T
+    }
+}
+
+unsafe impl<T> Pointee for [T] {
+    type Metadata = usize;
+
+    fn metadata(&self) -> Self::Metadata {
+        self.len()
+    }
+
+    fn from_raw_parts(data: *const u8, metadata: Self::Metadata) -> *const Self {
+        ptr::slice_from_raw_parts(data as *const T, metadata)
+    }
+}
+
+unsafe impl Pointee for str {
+    type Metadata = usize;
+
+    fn metadata(&self) -> Self::Metadata {
+        self.len()
+    }
+
+    fn from_raw_parts(data: *const u8, metadata: Self::Metadata) -> *const Self {
+        ptr::slice_from_raw_parts(data, metadata) as *const str
+    }
+}
+
+/**
+ The metadata of a trait object pointer, i.e. its vtable.
+
+ The vtable can't be separated from a trait object pointer on stable Rust, so this keeps a whole
+ pointer to the original value around, and swaps its address out when the pointer is rebuilt.
+ The kept pointer itself is never dereferenced.
+*/
+pub struct DynMetadata<Dyn: ?Sized>(*const Dyn);
+
+impl<Dyn: ?Sized> DynMetadata<Dyn> {
+    /// Capture the metadata of a pointer to `value`.
+    pub fn new(value: &Dyn) -> Self {
+        DynMetadata(value as *const Dyn)
+    }
+
+    /// Rebuild a pointer to `Dyn` from an address and the captured metadata.
+    pub fn attach(self, data: *const u8) -> *const Dyn {
+        const { assert!(mem::size_of::<*const Dyn>() == 2 * mem::size_of::<usize>()) };
+        let mut ptr = self.0;
+        // SAFETY: this assumes that the data address is the first word of a wide pointer, with the
+        // metadata in the second one. The layout of wide pointers isn't guaranteed, but it is the
+        // one rustc uses, which the assertions check as far as they can. Only the address is
+        // replaced, so the provenance of `data` is kept and the metadata stays untouched.
+        unsafe { *(&mut ptr as *mut *const Dyn as *mut *const u8) = data };
+        debug_assert_eq!(ptr.cast::<u8>(), data);
+        ptr
+    }
+}
+
+impl<Dyn: ?Sized> Clone for DynMetadata<Dyn> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Dyn: ?Sized> Copy for DynMetadata<Dyn> {}
+
+// SAFETY: the captured pointer is never dereferenced, it only serves as a vtable carrier.
+unsafe impl<Dyn: ?Sized> Send for DynMetadata<Dyn> {}
+unsafe impl<Dyn: ?Sized> Sync for DynMetadata<Dyn> {}
+
+/**
+ Implement [`Pointee`] for a trait object type, so it can be the target of a
+ [`Subptr`](crate::Subptr).
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{impl_dyn_pointee, Subrc};
+trait Validator {
+    fn validate(&self) -> bool;
+}
+
+impl_dyn_pointee!(dyn Validator);
+
+struct NonEmpty(&'static str);
+
+impl Validator for NonEmpty {
+    fn validate(&self) -> bool {
+        !self.0.is_empty()
+    }
+}
+
+struct Config {
+    name: NonEmpty,
+}
+
+let rc = Rc::new(Config { name: NonEmpty("foo") });
+let validator = Subrc::<_, dyn Validator>::new(rc, |config| &config.name);
+assert!(validator.validate());
+```
+ */
+#[macro_export]
+macro_rules! impl_dyn_pointee {
+    ($dyn:ty) => {
+        unsafe impl $crate::Pointee for $dyn {
+            type Metadata = $crate::DynMetadata<$dyn>;
+
+            fn metadata(&self) -> Self::Metadata {
+                $crate::DynMetadata::new(self)
+            }
+
+            fn from_raw_parts(data: *const u8, metadata: Self::Metadata) -> *const Self {
+                metadata.attach(data)
+            }
+        }
+    };
+}
+
+impl_dyn_pointee!(dyn Any);
+impl_dyn_pointee!(dyn Any + Send);
+impl_dyn_pointee!(dyn Any + Send + Sync);
@@ -0,0 +1,327 @@
+use core::marker::PhantomData;
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+/**
+ A struct with a field identified by the marker type `F`, so the field can be projected to by
+ type, with [`ProjectExt::project`] or [`Subptr::project`], instead of with a getter.
+
+ This allows generic code to be written over the fields of a struct. It is usually implemented
+ with `#[derive(Project)]` and its `#[project(markers = ...)]` attribute (with the `derive`
+ feature), which generates a marker type for each field.
+
+ # Safety
+ [`OFFSET`](Project::OFFSET) must be the offset of a field of type [`Output`](Project::Output)
+ within `Self`, e.g. as computed by [`offset_of`](core::mem::offset_of).
+
+ # Example
+```rust
+# use std::{fmt::Display, rc::Rc};
+# use subrc::{Project, ProjectExt, Subrc};
+struct State {
+    title: String,
+    count: u32,
+}
+
+struct Title;
+struct Count;
+
+unsafe impl Project<Title> for State {
+    type Output = String;
+    const OFFSET: usize = std::mem::offset_of!(State, title);
+}
+
+unsafe impl Project<Count> for State {
+    type Output = u32;
+    const OFFSET: usize = std::mem::offset_of!(State, count);
+}
+
+fn render<F>(field: Subrc<State, <State as Project<F>>::Output>) -> String
+where
+    State: Project<F>,
+    <State as Project<F>>::Output: Display,
+{
+    format!("[{}]", *field)
+}
+
+let rc = Rc::new(State { title: String::from("inbox"), count: 3 });
+assert_eq!(render::<Title>(rc.project::<Title>()), "[inbox]");
+assert_eq!(render::<Count>(rc.project::<Count>()), "[3]");
+```
+*/
+pub unsafe trait Project<F> {
+    /// The type of the field.
+    type Output;
+
+    /// The offset of the field within `Self`.
+    const OFFSET: usize;
+}
+
+/**
+ An extension of every [`SharedOwner`], projecting it to a field by type, see [`Project`].
+*/
+pub trait ProjectExt: SharedOwner {
+    /// Create a [`Subptr`] to the field of the owned value identified by the marker type `F`.
+    fn project<F>(&self) -> Subptr<Self, <Self::Target as Project<F>>::Output>
+    where
+        Self::Target: Project<F> + Sized;
+}
+
+impl<P: SharedOwner> ProjectExt for P {
+    fn project<F>(&self) -> Subptr<Self, <Self::Target as Project<F>>::Output>
+    where
+        Self::Target: Project<F> + Sized,
+    {
+        Subptr::whole(self.clone()).project::<F>()
+    }
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner,
+    U: Pointee,
+{
+    /**
+       Create a [`Subptr`] to the field of the owned value identified by the marker `field`, like
+       [`ProjectExt::project`].
+
+       Unlike with a getter, which is only checked to return a subregion of the owned value at
+       runtime, only the fields a [`Project`] implementation vouches for can be projected to, so
+       an invalid projection does not compile.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use std::mem::offset_of;
+           # use subrc::{Project, Subrc};
+           struct Account {
+               id: u64,
+               owner: String,
+           }
+
+           // As generated by `#[derive(Project)]` with `#[project(markers = fields)]`.
+           mod fields {
+               pub struct Id;
+               pub struct Owner;
+           }
+
+           unsafe impl Project<fields::Id> for Account {
+               type Output = u64;
+               const OFFSET: usize = offset_of!(Account, id);
+           }
+
+           unsafe impl Project<fields::Owner> for Account {
+               type Output = String;
+               const OFFSET: usize = offset_of!(Account, owner);
+           }
+
+           let rc = Rc::new(Account { id: 7, owner: String::from("ada") });
+           let owner = Subrc::at(rc.clone(), fields::Owner);
+           assert!(std::ptr::eq(&*owner, &rc.owner));
+           assert_eq!(*Subrc::at(rc, fields::Id), 7);
+       ```
+    */
+    pub fn at<F>(owner: P, field: F) -> Self
+    where
+        P::Target: Project<F, Output = U> + Sized,
+    {
+        let _ = field;
+        Subptr::whole(owner).project::<F>()
+    }
+
+    /**
+       Project this [`Subptr`] further down, to the field of its target identified by the marker
+       type `F`, like [`map`](Subptr::map) does with a getter. See [`Project`].
+    */
+    pub fn project<F>(&self) -> Subptr<P, U::Output>
+    where
+        U: Project<F>,
+    {
+        let ptr = (self.get() as *const U).cast::<u8>();
+        // SAFETY: the field lies at `OFFSET` within the target, see `Project`.
+        unsafe { Subptr::from_raw_parts(self.owner().clone(), ptr.add(U::OFFSET).cast()) }
+    }
+}
+
+/**
+* The precomputed offset of a field of type `U` within a `T`, a projection descriptor which can be
+* created, composed and used in const contexts, and later applied to owners.
+*
+* # Example
+```rust
+# use std::{mem::offset_of, rc::Rc};
+# use subrc::FieldOffset;
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+// SAFETY: `offset_of!` computes the offset of the fields, which are `i32`s.
+const AXES: [FieldOffset<Point, i32>; 2] = unsafe {
+    [FieldOffset::new(offset_of!(Point, x)), FieldOffset::new(offset_of!(Point, y))]
+};
+
+static ORIGIN: Point = Point { x: 0, y: 0 };
+const ORIGIN_Y: &i32 = AXES[1].get(&ORIGIN);
+assert_eq!(*ORIGIN_Y, 0);
+
+let rc = Rc::new(Point { x: 3, y: 4 });
+let y = AXES[1].apply(&rc);
+assert!(std::ptr::eq(&*y, &rc.y));
+```
+*/
+pub struct FieldOffset<T, U> {
+    offset: usize,
+    _types: PhantomData<fn(&T) -> &U>,
+}
+
+impl<T, U> FieldOffset<T, U> {
+    /**
+       Create a [`FieldOffset`] from the `offset` of a field within a `T`.
+
+       # Safety
+       A field of type `U` must lie at `offset` within every `T`, e.g. as computed by
+       [`offset_of`](core::mem::offset_of).
+    */
+    pub const unsafe fn new(offset: usize) -> Self {
+        FieldOffset {
+            offset,
+            _types: PhantomData,
+        }
+    }
+
+    /// Create a [`FieldOffset`] to the field identified by the marker type `F`, see [`Project`].
+    pub const fn of<F>() -> Self
+    where
+        T: Project<F, Output = U>,
+    {
+        // SAFETY: the offset is vouched for by the `Project` implementation.
+        unsafe { Self::new(<T as Project<F>>::OFFSET) }
+    }
+
+    /// Get the offset of the field.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Compose this offset with the offset of a field within the `U`.
+    pub const fn then<V>(self, next: FieldOffset<U, V>) -> FieldOffset<T, V> {
+        // SAFETY: the field within the `U` lies at the sum of both offsets within the `T`.
+        unsafe { FieldOffset::new(self.offset + next.offset) }
+    }
+
+    /// Get the field of `t`.
+    pub const fn get<'t>(&self, t: &'t T) -> &'t U {
+        let ptr = (t as *const T).cast::<u8>();
+        // SAFETY: the field lies at `offset` within `t`, see `new`.
+        unsafe { &*ptr.add(self.offset).cast::<U>() }
+    }
+
+    /// Create a [`Subptr`] to the field of the value owned by `owner`.
+    pub fn apply<P>(&self, owner: &P) -> Subptr<P, U>
+    where
+        P: SharedOwner<Target = T>,
+        U: Pointee,
+    {
+        let ptr = self.get(owner) as *const U;
+        // SAFETY: the field lies within the owned value, see `new`.
+        unsafe { Subptr::from_raw_parts(owner.clone(), ptr) }
+    }
+}
+
+impl<T, U> Clone for FieldOffset<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, U> Copy for FieldOffset<T, U> {}
+
+#[cfg(test)]
+mod tests {
+    use std::{mem::offset_of, rc::Rc, sync::Arc};
+
+    use super::{FieldOffset, Project, ProjectExt};
+    use crate::Subrc;
+
+    struct Outer {
+        _id: u8,
+        inner: Inner,
+    }
+
+    struct Inner {
+        _flag: bool,
+        value: u32,
+    }
+
+    struct InnerField;
+    struct ValueField;
+
+    unsafe impl Project<InnerField> for Outer {
+        type Output = Inner;
+        const OFFSET: usize = offset_of!(Outer, inner);
+    }
+
+    unsafe impl Project<ValueField> for Inner {
+        type Output = u32;
+        const OFFSET: usize = offset_of!(Inner, value);
+    }
+
+    fn outer() -> Outer {
+        Outer {
+            _id: 1,
+            inner: Inner {
+                _flag: true,
+                value: 2,
+            },
+        }
+    }
+
+    #[test]
+    fn test_project_by_type() {
+        let rc = Rc::new(outer());
+        let inner = rc.project::<InnerField>();
+        assert!(std::ptr::eq(&*inner, &rc.inner));
+
+        let value = inner.project::<ValueField>();
+        assert!(std::ptr::eq(&*value, &rc.inner.value));
+        assert_eq!(Rc::strong_count(&rc), 3);
+    }
+
+    #[test]
+    fn test_project_arc_by_type() {
+        let arc = Arc::new(outer());
+        let value = arc.project::<InnerField>().project::<ValueField>();
+        assert_eq!(*value, 2);
+    }
+
+    #[test]
+    fn test_field_offset() {
+        const VALUE: FieldOffset<Outer, u32> =
+            FieldOffset::of::<InnerField>().then(FieldOffset::of::<ValueField>());
+        static OUTER: Outer = Outer {
+            _id: 1,
+            inner: Inner {
+                _flag: true,
+                value: 2,
+            },
+        };
+        const STATIC_VALUE: &u32 = VALUE.get(&OUTER);
+        assert!(std::ptr::eq(STATIC_VALUE, &OUTER.inner.value));
+
+        let arc = Arc::new(outer());
+        assert!(std::ptr::eq(&*VALUE.apply(&arc), &arc.inner.value));
+        assert_eq!(
+            VALUE.offset(),
+            offset_of!(Outer, inner) + offset_of!(Inner, value)
+        );
+    }
+
+    #[test]
+    fn test_at() {
+        let rc = Rc::new(outer());
+        let inner = Subrc::at(rc.clone(), InnerField);
+        assert!(std::ptr::eq(&*inner, &rc.inner));
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{Pointee, SharedOwner, Subptr};
+
+/**
+* An extension of every [`SharedOwner`], creating projections with a getter directly on the
+* owner, like [`Subptr::new`].
+*
+* The methods aren't named `project`, which projects to a field by type, see
+* [`ProjectExt`](crate::ProjectExt).
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{Projectable, Subrc};
+struct Window {
+    title: String,
+    size: (u32, u32),
+}
+
+let rc = Rc::new(Window { title: String::from("main"), size: (640, 480) });
+let title: Subrc<Window, String> = rc.project_ref(|window| &window.title);
+let size = rc.project_into(|window| &window.size);
+assert_eq!(*title, "main");
+assert_eq!(*size, (640, 480));
+```
+*/
+pub trait Projectable: SharedOwner {
+    /**
+       Create a [`Subptr`] to the subregion of the owned value returned by `getter`, consuming
+       this owner.
+
+       # Panics
+       Like [`Subptr::new`], `getter` must return a subregion of the owned value.
+    */
+    fn project_into<U, F>(self, getter: F) -> Subptr<Self, U>
+    where
+        U: ?Sized + Pointee,
+        F: FnOnce(&Self::Target) -> &U;
+
+    /**
+       Like [`project_into`](Projectable::project_into), but clones this owner instead of
+       consuming it.
+    */
+    fn project_ref<U, F>(&self, getter: F) -> Subptr<Self, U>
+    where
+        U: ?Sized + Pointee,
+        F: FnOnce(&Self::Target) -> &U;
+}
+
+impl<P: SharedOwner> Projectable for P {
+    #[track_caller]
+    fn project_into<U, F>(self, getter: F) -> Subptr<Self, U>
+    where
+        U: ?Sized + Pointee,
+        F: FnOnce(&Self::Target) -> &U,
+    {
+        Subptr::new(self, getter)
+    }
+
+    #[track_caller]
+    fn project_ref<U, F>(&self, getter: F) -> Subptr<Self, U>
+    where
+        U: ?Sized + Pointee,
+        F: FnOnce(&Self::Target) -> &U,
+    {
+        Subptr::new(self.clone(), getter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use crate::Projectable;
+
+    #[test]
+    fn test_project_ref() {
+        let rc = Rc::new((1u8, [2u16, 3]));
+        let second = rc.project_ref(|t| &t.1[1]);
+        assert!(std::ptr::eq(&*second, &rc.1[1]));
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+
+    #[test]
+    fn test_project_into() {
+        let arc = Arc::new((1u8, String::from("two")));
+        let first = arc.clone().project_into(|t| &t.0);
+        let second = arc.project_into(|t| &t.1);
+        assert_eq!(*first, 1);
+        assert_eq!(*second, "two");
+    }
+}
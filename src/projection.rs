@@ -0,0 +1,273 @@
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+
+use crate::{Lens, Pointee, ProjectionError, SharedOwner, Subptr};
+
+/**
+* A reusable projection from a `T` to a part `U` of it, which can be defined once, composed with
+* [`then`](Projection::then), stored, and later [applied](Projection::apply) to many owners.
+*
+* A projection from a non-capturing closure can be stored as a `Projection<T, U>`, which uses a
+* function pointer, and a composition of two of those with their [`Then`]. Any projection can
+* also be stored as a [`BoxedProjection`], with [`boxed`](Projection::boxed). A [`Projection`] is
+* also a [`Lens`].
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{Projection, Subrc};
+struct Config {
+    server: Server,
+}
+
+struct Server {
+    port: u16,
+}
+
+let server = Projection::of(|config: &Config| &config.server);
+let port = server.then(Projection::of(|server: &Server| &server.port));
+
+let first = Rc::new(Config { server: Server { port: 80 } });
+let second = Rc::new(Config { server: Server { port: 443 } });
+let ports: Vec<Subrc<Config, u16>> = vec![port.apply(&first), port.apply(&second)];
+assert_eq!(*ports[1], 443);
+```
+*/
+pub struct Projection<T: ?Sized, U: ?Sized, F = fn(&T) -> &U> {
+    getter: F,
+    _types: PhantomData<fn(&T) -> &U>,
+}
+
+/// A [`Projection`] with a boxed getter, created by [`Projection::boxed`].
+pub type BoxedProjection<'f, T, U> = Projection<T, U, Box<dyn Fn(&T) -> &U + 'f>>;
+
+impl<T, U, F> Projection<T, U, F>
+where
+    T: ?Sized,
+    U: ?Sized + Pointee,
+    F: Fn(&T) -> &U,
+{
+    /// Create a [`Projection`] to the subregion of a `T` returned by `getter`.
+    pub fn of(getter: F) -> Self {
+        Self::from_getter(getter)
+    }
+}
+
+impl<T, U, F> Projection<T, U, F>
+where
+    T: ?Sized,
+    U: ?Sized + Pointee,
+    F: Getter<T, U>,
+{
+    fn from_getter(getter: F) -> Self {
+        Projection {
+            getter,
+            _types: PhantomData,
+        }
+    }
+
+    /// Get the part of `t` this projection accesses.
+    pub fn get<'t>(&self, t: &'t T) -> &'t U {
+        self.getter.get(t)
+    }
+
+    /// Compose this projection with `next`, projecting further down into its `U`, which must be
+    /// `'static` (see [`Then`]).
+    pub fn then<V, G>(self, next: Projection<U, V, G>) -> Projection<T, V, Then<F, G, U>>
+    where
+        U: 'static,
+        V: ?Sized + Pointee,
+        G: Getter<U, V>,
+    {
+        Projection::from_getter(Then {
+            first: self.getter,
+            second: next.getter,
+            _via: PhantomData,
+        })
+    }
+
+    /// Erase the type of the getter, so the projection can be stored with a nameable type.
+    pub fn boxed<'f>(self) -> BoxedProjection<'f, T, U>
+    where
+        F: 'f,
+    {
+        let getter = self.getter;
+        Projection::of(Box::new(move |t: &T| getter.get(t)))
+    }
+
+    /**
+       Apply this projection to the value owned by `owner`, like [`Subptr::new`].
+
+       # Panics
+       Like [`Subptr::new`], the getter must return a subregion of the owned value. Use
+       [`try_apply`](Projection::try_apply) to handle this case gracefully.
+    */
+    #[track_caller]
+    pub fn apply<P>(&self, owner: &P) -> Subptr<P, U>
+    where
+        P: SharedOwner<Target = T>,
+    {
+        Subptr::from_lens(owner.clone(), self)
+    }
+
+    /**
+       Like [`apply`](Projection::apply), but returns a [`ProjectionError`] instead of panicking
+       if the getter does not return a subregion of the owned value.
+    */
+    pub fn try_apply<P>(&self, owner: &P) -> Result<Subptr<P, U>, ProjectionError>
+    where
+        P: SharedOwner<Target = T>,
+    {
+        Subptr::try_from_lens(owner.clone(), self)
+    }
+}
+
+impl<T, U, F> Lens<T, U> for Projection<T, U, F>
+where
+    T: ?Sized,
+    U: ?Sized + Pointee,
+    F: Getter<T, U>,
+{
+    fn get<'t>(&self, data: &'t T) -> &'t U {
+        self.getter.get(data)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, F: Clone> Clone for Projection<T, U, F> {
+    fn clone(&self) -> Self {
+        Projection {
+            getter: self.getter.clone(),
+            _types: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, F: Copy> Copy for Projection<T, U, F> {}
+
+/**
+ The getter of a [`Projection`], returning the part `U` of a `T` it accesses. Implemented for
+ closures and functions, and for compositions of getters ([`Then`]).
+*/
+pub trait Getter<T: ?Sized, U: ?Sized> {
+    /// Get the part of `t` this getter accesses.
+    fn get<'t>(&self, t: &'t T) -> &'t U;
+}
+
+impl<T: ?Sized, U: ?Sized, F: Fn(&T) -> &U> Getter<T, U> for F {
+    fn get<'t>(&self, t: &'t T) -> &'t U {
+        self(t)
+    }
+}
+
+/**
+ The composition of a getter from a `T` to a `U` with one from the `U` to a `V`, created by
+ [`Projection::then`].
+
+ The intermediate `U` must be `'static`, as the compiler cannot otherwise tell that it outlives
+ every `T` it is a part of.
+*/
+pub struct Then<F, G, U: ?Sized> {
+    first: F,
+    second: G,
+    _via: PhantomData<fn(&U)>,
+}
+
+impl<T, U, V, F, G> Getter<T, V> for Then<F, G, U>
+where
+    T: ?Sized,
+    U: ?Sized + 'static,
+    V: ?Sized,
+    F: Getter<T, U>,
+    G: Getter<U, V>,
+{
+    fn get<'t>(&self, t: &'t T) -> &'t V {
+        self.second.get(self.first.get(t))
+    }
+}
+
+impl<F: Clone, G: Clone, U: ?Sized> Clone for Then<F, G, U> {
+    fn clone(&self) -> Self {
+        Then {
+            first: self.first.clone(),
+            second: self.second.clone(),
+            _via: PhantomData,
+        }
+    }
+}
+
+impl<F: Copy, G: Copy, U: ?Sized> Copy for Then<F, G, U> {}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use super::{BoxedProjection, Then};
+    use crate::{Projection, Subrc};
+
+    struct Outer {
+        _id: u8,
+        inner: Inner,
+    }
+
+    struct Inner {
+        name: String,
+        values: [u32; 3],
+    }
+
+    fn outer(name: &str) -> Outer {
+        Outer {
+            _id: 1,
+            inner: Inner {
+                name: String::from(name),
+                values: [1, 2, 3],
+            },
+        }
+    }
+
+    #[test]
+    fn test_projection_apply() {
+        let inner: Projection<Outer, Inner> = Projection::of(|outer| &outer.inner);
+        let values = inner.then(Projection::of(|inner: &Inner| &inner.values[1..]));
+
+        let first = Rc::new(outer("first"));
+        let second = Rc::new(outer("second"));
+        let projected: Vec<Subrc<Outer, [u32]>> = [&first, &second]
+            .into_iter()
+            .map(|rc| values.apply(rc))
+            .collect();
+        assert!(std::ptr::eq(&*projected[0], &first.inner.values[1..]));
+        assert!(std::ptr::eq(&*projected[1], &second.inner.values[1..]));
+        assert!(std::ptr::eq(&*inner.apply(&first), &first.inner));
+        assert_eq!(Rc::strong_count(&first), 2);
+    }
+
+    #[test]
+    fn test_projection_boxed() {
+        static NAME: String = String::new();
+
+        let projections: Vec<BoxedProjection<Outer, String>> = vec![
+            Projection::of(|outer: &Outer| &outer.inner)
+                .then(Projection::of(|inner: &Inner| &inner.name))
+                .boxed(),
+            Projection::of(|_: &Outer| &NAME).boxed(),
+        ];
+
+        let arc = Arc::new(outer("name"));
+        assert_eq!(&*projections[0].apply(&arc), "name");
+        assert!(projections[1].try_apply(&arc).is_err());
+    }
+
+    #[test]
+    fn test_projection_fn_pointers() {
+        type Name =
+            Projection<Outer, String, Then<fn(&Outer) -> &Inner, fn(&Inner) -> &String, Inner>>;
+
+        let inner: Projection<Outer, Inner> = Projection::of(|outer| &outer.inner);
+        let name: Name = inner.then(Projection::of(|inner| &inner.name));
+        let copy = name;
+
+        let rc = Rc::new(outer("name"));
+        assert!(std::ptr::eq(&*copy.apply(&rc), &rc.inner.name));
+        assert!(std::ptr::eq(&*name.apply(&rc), &rc.inner.name));
+    }
+}
@@ -0,0 +1,299 @@
+use core::{any::type_name, mem};
+
+use crate::{
+    subptr::{get_offset, invalid_projection_to},
+    Pointee, ProjectionError, SharedOwner, Subptr,
+};
+
+/**
+* A builder creating many [`Subptr`]s to subregions of the same owned value at once.
+*
+* Every [`field`](Projector::field) adds one projection, and [`finish`](Projector::finish) returns
+* all of them as a tuple, in order. The projections are validated together: if any of them is
+* invalid, none is returned.
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{Projector, Subrc};
+struct Config {
+    name: String,
+    port: u16,
+    verbose: bool,
+}
+
+let rc = Rc::new(Config { name: String::from("server"), port: 8080, verbose: true });
+let (name, port, verbose): (Subrc<Config, String>, Subrc<Config, u16>, Subrc<Config, bool>) =
+    Projector::new(rc.clone())
+        .field(|config| &config.name)
+        .field(|config| &config.port)
+        .field(|config| &config.verbose)
+        .finish();
+assert_eq!(*port, 8080);
+assert_eq!(Rc::strong_count(&rc), 4);
+```
+*/
+pub struct Projector<P, L = ()> {
+    owner: P,
+    projections: L,
+    error: Option<InvalidField>,
+}
+
+/// The first invalid projection of a [`Projector`], kept to be reported by `finish`.
+struct InvalidField {
+    error: ProjectionError,
+    target: &'static str,
+    region: (usize, usize),
+}
+
+impl<P> Projector<P>
+where
+    P: SharedOwner,
+{
+    /// Create a [`Projector`] for the value owned by `owner`.
+    pub fn new(owner: P) -> Self {
+        Projector {
+            owner,
+            projections: (),
+            error: None,
+        }
+    }
+}
+
+impl<P, L> Projector<P, L>
+where
+    P: SharedOwner,
+{
+    /**
+       Add a projection to the subregion returned by `getter`, like [`Subptr::new`].
+
+       An invalid projection is only reported by [`finish`](Projector::finish) or
+       [`try_finish`](Projector::try_finish).
+    */
+    pub fn field<U, F>(self, getter: F) -> Projector<P, L::Output>
+    where
+        U: ?Sized + Pointee,
+        F: FnOnce(&P::Target) -> &U,
+        L: Append<Option<Subptr<P, U>>>,
+    {
+        let u = getter(&self.owner);
+        let (projection, invalid) = match get_offset(&*self.owner, u) {
+            // SAFETY: the target was checked to lie within the owned value, like in `try_new`.
+            Ok(_) => (
+                Some(unsafe { Subptr::from_raw_parts(self.owner.clone(), u as *const U) }),
+                None,
+            ),
+            Err(error) => (
+                None,
+                Some(InvalidField {
+                    error,
+                    target: type_name::<U>(),
+                    region: ((u as *const U).addr(), mem::size_of_val(u)),
+                }),
+            ),
+        };
+        Projector {
+            owner: self.owner,
+            projections: self.projections.append(projection),
+            error: self.error.or(invalid),
+        }
+    }
+
+    /**
+       Return all the projections as a tuple, or the error of the first invalid one.
+    */
+    pub fn try_finish(self) -> Result<L::Output, ProjectionError>
+    where
+        L: Transpose,
+    {
+        match self.error {
+            Some(invalid) => Err(invalid.error),
+            None => Ok(self
+                .projections
+                .transpose()
+                .expect("all projections are valid")),
+        }
+    }
+
+    /**
+       Return all the projections as a tuple.
+
+       # Panics
+       Panics if any of the projections is invalid, see [`Subptr::new`].
+    */
+    #[track_caller]
+    pub fn finish(self) -> L::Output
+    where
+        L: Transpose,
+    {
+        match self.error {
+            Some(invalid) => {
+                invalid_projection_to(invalid.error, &*self.owner, invalid.target, invalid.region)
+            }
+            None => self
+                .projections
+                .transpose()
+                .expect("all projections are valid"),
+        }
+    }
+}
+
+/**
+ Append an element to a tuple. Implemented for tuples of up to 11 elements, which limits a
+ [`Projector`] to 12 fields.
+*/
+pub trait Append<X> {
+    /// The tuple with `X` appended.
+    type Output;
+
+    /// Append `x` to the tuple.
+    fn append(self, x: X) -> Self::Output;
+}
+
+/**
+ Turn a tuple of [`Option`]s into an [`Option`] of a tuple. Implemented for tuples of up to 12
+ elements.
+*/
+pub trait Transpose {
+    /// The tuple of the [`Option`]s' contents.
+    type Output;
+
+    /// Return the contents of all the [`Option`]s if they are all [`Some`].
+    fn transpose(self) -> Option<Self::Output>;
+}
+
+macro_rules! impl_tuples {
+    ($($name:ident)*) => {
+        #[allow(non_snake_case)]
+        impl<$($name,)*> Transpose for ($(Option<$name>,)*) {
+            type Output = ($($name,)*);
+
+            fn transpose(self) -> Option<Self::Output> {
+                let ($($name,)*) = self;
+                Some(($($name?,)*))
+            }
+        }
+    };
+}
+
+macro_rules! impl_append {
+    ($($name:ident)*) => {
+        #[allow(non_snake_case)]
+        impl<$($name,)* X> Append<X> for ($($name,)*) {
+            type Output = ($($name,)* X,);
+
+            fn append(self, x: X) -> Self::Output {
+                let ($($name,)*) = self;
+                ($($name,)* x,)
+            }
+        }
+    };
+}
+
+impl_tuples!();
+impl_tuples!(A);
+impl_tuples!(A B);
+impl_tuples!(A B C);
+impl_tuples!(A B C D);
+impl_tuples!(A B C D E);
+impl_tuples!(A B C D E F);
+impl_tuples!(A B C D E F G);
+impl_tuples!(A B C D E F G H);
+impl_tuples!(A B C D E F G H I);
+impl_tuples!(A B C D E F G H I J);
+impl_tuples!(A B C D E F G H I J K);
+impl_tuples!(A B C D E F G H I J K L);
+
+impl_append!();
+impl_append!(A);
+impl_append!(A B);
+impl_append!(A B C);
+impl_append!(A B C D);
+impl_append!(A B C D E);
+impl_append!(A B C D E F);
+impl_append!(A B C D E F G);
+impl_append!(A B C D E F G H);
+impl_append!(A B C D E F G H I);
+impl_append!(A B C D E F G H I J);
+impl_append!(A B C D E F G H I J K);
+
+#[cfg(test)]
+mod tests {
+    use std::{any::type_name, sync::Arc};
+
+    use crate::{ProjectionError, Projector};
+
+    struct Foo {
+        a: u8,
+        b: [u16; 3],
+        c: &'static str,
+    }
+
+    fn foo() -> Arc<Foo> {
+        Arc::new(Foo {
+            a: 1,
+            b: [2, 3, 4],
+            c: "five",
+        })
+    }
+
+    #[test]
+    fn test_projector() {
+        let arc = foo();
+        let (a, b, c) = Projector::new(arc.clone())
+            .field(|foo| &foo.a)
+            .field(|foo| &foo.b[1..])
+            .field(|foo| &foo.c)
+            .finish();
+        assert!(std::ptr::eq(&*a, &arc.a));
+        assert!(std::ptr::eq(&*b, &arc.b[1..]));
+        assert!(std::ptr::eq(&*c, &arc.c));
+        assert_eq!(Arc::strong_count(&arc), 4);
+    }
+
+    #[test]
+    fn test_projector_validated_together() {
+        static OTHER: u8 = 0;
+        let arc = foo();
+        let result = Projector::new(arc.clone())
+            .field(|foo| &foo.a)
+            .field(|_| &OTHER)
+            .field(|foo| &foo.b)
+            .try_finish();
+        assert!(matches!(
+            result,
+            Err(ProjectionError::BeforeOwner | ProjectionError::OutOfBounds)
+        ));
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+
+    #[test]
+    fn test_projector_finish_message() {
+        static OTHER: u32 = 0;
+        let arc = foo();
+        let owner = Arc::as_ptr(&arc).addr();
+        let error = std::panic::catch_unwind(|| {
+            Projector::new(arc.clone())
+                .field(|foo| &foo.a)
+                .field(|_| &OTHER)
+                .finish()
+        })
+        .unwrap_err();
+        let message = error.downcast::<String>().unwrap();
+        assert!(message.starts_with("getter did not return portion of the object: "));
+        assert!(message.contains(&format!(
+            "a `u32` at {:#x}..",
+            std::ptr::addr_of!(OTHER).addr()
+        )));
+        assert!(message.contains(&format!(
+            "from the owned `{}` at {:#x}..",
+            type_name::<Foo>(),
+            owner
+        )));
+    }
+
+    #[test]
+    fn test_projector_empty() {
+        let () = Projector::new(foo()).finish();
+    }
+}
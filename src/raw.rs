@@ -0,0 +1,135 @@
+use std::{
+    any::TypeId,
+    boxed::Box,
+    collections::BTreeMap,
+    sync::{Mutex, PoisonError},
+    vec::Vec,
+};
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+// A `Subptr` can't be recovered from the address of its target alone: unlike `Rc::into_raw`, the
+// target may live anywhere within (or behind) the owned value, so the owner is unknown. Instead,
+// `into_raw` moves the whole `Subptr` to the heap and registers it under the target address (and
+// its own type, since several projections can share an address), for `from_raw` to pick it up
+// later. The boxes are kept as pointers rather than addresses, so they keep their provenance.
+type Key = (usize, TypeId);
+
+struct Boxed(*mut ());
+
+// SAFETY: a box is only turned back into a `Subptr` by `from_raw`, whose contract requires it to
+// happen on the right thread for non-`Send` owners.
+unsafe impl Send for Boxed {}
+
+static REGISTRY: Mutex<BTreeMap<Key, Vec<Boxed>>> = Mutex::new(BTreeMap::new());
+
+fn key<P: 'static, U: ?Sized + Pointee + 'static>(ptr: *const U) -> Key {
+    (ptr.addr(), TypeId::of::<Subptr<P, U>>())
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner + 'static,
+    U: ?Sized + Pointee + 'static,
+{
+    /**
+       Consume the [`Subptr`], returning a raw pointer to its target, like [`Rc::into_raw`](alloc::rc::Rc::into_raw).
+
+       The owner is kept alive until the pointer is turned back into a [`Subptr`] with
+       [`from_raw`](Subptr::from_raw), which must eventually be done to avoid leaking it.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               value: i32,
+           }
+
+           let rc = Rc::new(Foo { value: 42 });
+           let ptr = Subrc::into_raw(subrc!(rc.value));
+           assert_eq!(unsafe { *ptr }, 42);
+
+           let subrc: Subrc<Foo, i32> = unsafe { Subrc::from_raw(ptr) };
+           assert!(std::ptr::eq(&*subrc, &rc.value));
+       ```
+    */
+    pub fn into_raw(this: Self) -> *const U {
+        let ptr = this.get() as *const U;
+        let boxed = Boxed(Box::into_raw(Box::new(this)).cast());
+        REGISTRY
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key::<P, U>(ptr))
+            .or_default()
+            .push(boxed);
+        ptr
+    }
+
+    /**
+       Recover a [`Subptr`] from a raw pointer returned by [`into_raw`](Subptr::into_raw).
+
+       # Safety
+       `ptr` must have been returned by [`into_raw`](Subptr::into_raw) on a `Subptr<P, U>` of this
+       very type, and each such pointer may only be recovered once. If `P` is not [`Send`] (like
+       [`Rc`](alloc::rc::Rc)), it must be recovered on the thread it was created on.
+
+       # Panics
+       Panics if there is no such pointer pending recovery.
+    */
+    pub unsafe fn from_raw(ptr: *const U) -> Self {
+        let boxed = {
+            let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+            let key = key::<P, U>(ptr);
+            let entries = registry
+                .get_mut(&key)
+                .expect("pointer was not returned by `Subptr::into_raw`");
+            let boxed = entries.pop().expect("entries are never left empty");
+            if entries.is_empty() {
+                registry.remove(&key);
+            }
+            boxed
+        };
+        *Box::from_raw(boxed.0.cast::<Self>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use crate::{Subarc, Subrc};
+
+    #[test]
+    fn test_raw_roundtrip_keeps_owner_alive() {
+        let rc = Rc::new((1u8, [2u16, 3]));
+        let ptr = Subrc::into_raw(Subrc::new(rc.clone(), |t| &t.1[1]));
+        assert_eq!(Rc::strong_count(&rc), 2);
+
+        let subrc: Subrc<(u8, [u16; 2]), u16> = unsafe { Subrc::from_raw(ptr) };
+        assert!(std::ptr::eq(&*subrc, &rc.1[1]));
+        drop(subrc);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn test_raw_shared_address() {
+        type Owner = ([u32; 2], u8);
+        let arc: Arc<Owner> = Arc::new(([1, 2], 3));
+        let array = Subarc::into_raw(Subarc::new(arc.clone(), |t| &t.0));
+        let slice = Subarc::into_raw(Subarc::<_, [u32]>::new(arc.clone(), |t| &t.0[..]));
+        let first = Subarc::into_raw(Subarc::new(arc.clone(), |t| &t.0[0]));
+        let first_again = Subarc::into_raw(Subarc::new(arc.clone(), |t| &t.0[0]));
+
+        let slice: Subarc<Owner, [u32]> = unsafe { Subarc::from_raw(slice) };
+        assert_eq!(&*slice, &[1, 2]);
+        let array: Subarc<Owner, [u32; 2]> = unsafe { Subarc::from_raw(array) };
+        assert_eq!(&*array, &[1, 2]);
+        for first in [first, first_again] {
+            let first: Subarc<Owner, u32> = unsafe { Subarc::from_raw(first) };
+            assert_eq!(*first, 1);
+        }
+        drop((slice, array));
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+}
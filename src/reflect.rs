@@ -0,0 +1,172 @@
+use alloc::{boxed::Box, string::String};
+use core::any::Any;
+
+use crate::{SharedOwner, Subptr};
+
+/**
+* A minimal runtime reflection of the fields of a value, for projections by path with
+* [`project_path`](Subptr::project_path), e.g. in scripting or editor tooling.
+*
+* Leaf values only need an empty implementation, which is provided for the primitive types and
+* [`String`]. A `dyn Reflect` projection can be turned back into a typed one with
+* [`downcast`](Subptr::downcast).
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{Reflect, Subrc};
+struct Settings {
+    video: Video,
+}
+
+struct Video {
+    width: u32,
+    height: u32,
+}
+
+impl Reflect for Settings {
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        match name {
+            "video" => Some(&self.video),
+            _ => None,
+        }
+    }
+}
+
+impl Reflect for Video {
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        match name {
+            "width" => Some(&self.width),
+            "height" => Some(&self.height),
+            _ => None,
+        }
+    }
+}
+
+let rc = Rc::new(Settings { video: Video { width: 1920, height: 1080 } });
+let height = Subrc::project_path(rc.clone(), "video.height").unwrap();
+let height = Subrc::downcast::<u32>(height).ok().unwrap();
+assert!(std::ptr::eq(&*height, &rc.video.height));
+assert!(Subrc::project_path(rc, "video.depth").is_none());
+```
+*/
+pub trait Reflect: Any {
+    /// Get the field called `name`, if any. Defaults to having no fields.
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        let _ = name;
+        None
+    }
+}
+
+macro_rules! impl_reflect_leaf {
+    ($($ty:ty),*) => {
+        $(impl Reflect for $ty {})*
+    };
+}
+
+impl_reflect_leaf!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    String
+);
+
+crate::impl_dyn_pointee!(dyn Reflect);
+
+impl<T: Reflect + ?Sized> Reflect for Box<T> {
+    fn field(&self, name: &str) -> Option<&dyn Reflect> {
+        (**self).field(name)
+    }
+}
+
+impl<P> Subptr<P, dyn Reflect>
+where
+    P: SharedOwner,
+    P::Target: Reflect + Sized,
+{
+    /**
+       Create a [`Subptr`] to the field of the owned value at the dot-separated `path` of field
+       names, e.g. `"settings.video.resolution"`, or [`None`] if there is no such field. An empty
+       path projects to the whole value.
+
+       The fields are looked up at runtime, see [`Reflect`]. Like with
+       [`new_indirect`](Subptr::new_indirect), they may lie behind indirections.
+    */
+    pub fn project_path(owner: P, path: &str) -> Option<Self> {
+        let mut field: &dyn Reflect = &*owner;
+        for name in path.split('.').filter(|name| !name.is_empty()) {
+            field = field.field(name)?;
+        }
+        let ptr = field as *const dyn Reflect;
+        // SAFETY: every field was borrowed from the owned value, which `owner` keeps alive and
+        // shared, see `new_indirect`.
+        Some(unsafe { Subptr::from_raw_parts(owner, ptr) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use crate::{Reflect, Subarc, Subrc};
+
+    struct Node {
+        name: String,
+        child: Option<Box<Node>>,
+    }
+
+    impl Reflect for Node {
+        fn field(&self, name: &str) -> Option<&dyn Reflect> {
+            match name {
+                "name" => Some(&self.name),
+                "child" => self.child.as_ref().map(|child| child as &dyn Reflect),
+                _ => None,
+            }
+        }
+    }
+
+    fn tree() -> Node {
+        Node {
+            name: String::from("root"),
+            child: Some(Box::new(Node {
+                name: String::from("leaf"),
+                child: None,
+            })),
+        }
+    }
+
+    #[test]
+    fn test_project_path() {
+        let rc = Rc::new(tree());
+        let name = Subrc::project_path(rc.clone(), "child.name").unwrap();
+        let name = Subrc::downcast::<String>(name).ok().unwrap();
+        assert!(std::ptr::eq(&*name, &rc.child.as_ref().unwrap().name));
+
+        let root = Subrc::project_path(rc.clone(), "").unwrap();
+        assert!(Subrc::downcast::<Node>(root).is_ok());
+        assert!(Subrc::project_path(rc.clone(), "child.child.name").is_none());
+        assert!(Subrc::project_path(rc, "name.length").is_none());
+    }
+
+    #[test]
+    fn test_project_path_arc() {
+        let arc = Arc::new(tree());
+        let name = Subarc::project_path(arc.clone(), "name").unwrap();
+        let name = Subarc::downcast::<String>(name).ok().unwrap();
+        assert!(std::ptr::eq(&*name, &arc.name));
+    }
+}
@@ -0,0 +1,192 @@
+use alloc::{boxed::Box, collections::BTreeMap, string::String};
+use core::any::{Any, TypeId};
+
+use crate::{projection::BoxedProjection, Pointee, Projection, SharedOwner, Subptr};
+
+/**
+* A registry of named projections per owned type, so a projection can be referred to, and
+* persisted, by name, e.g. as a [`ProjectionKey`], and reconstructed later.
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{ProjectionRegistry, Subrc};
+struct Config {
+    db: Db,
+}
+
+struct Db {
+    url: String,
+}
+
+let mut registry = ProjectionRegistry::new();
+registry.register("db_url", |config: &Config| &config.db.url);
+
+let rc = Rc::new(Config { db: Db { url: String::from("postgres://localhost") } });
+let url: Subrc<Config, String> = registry.resolve(&rc, "db_url").unwrap();
+assert!(std::ptr::eq(&*url, &rc.db.url));
+```
+*/
+#[derive(Default)]
+pub struct ProjectionRegistry {
+    projections: BTreeMap<TypeId, BTreeMap<String, Box<dyn Any>>>,
+}
+
+impl ProjectionRegistry {
+    /// Create an empty [`ProjectionRegistry`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+       Register the projection of a `T` returned by `getter` as `name`, replacing any projection
+       of a `T` previously registered with the same name.
+    */
+    pub fn register<T, U, F>(&mut self, name: impl Into<String>, getter: F)
+    where
+        T: Any,
+        U: ?Sized + Pointee + Any,
+        F: Fn(&T) -> &U + 'static,
+    {
+        let projection: BoxedProjection<'static, T, U> = Projection::of(getter).boxed();
+        self.projections
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .insert(name.into(), Box::new(projection));
+    }
+
+    /// Return whether a projection of a `T` is registered as `name`.
+    pub fn contains<T: Any>(&self, name: &str) -> bool {
+        self.get_any::<T>(name).is_some()
+    }
+
+    fn get_any<T: Any>(&self, name: &str) -> Option<&dyn Any> {
+        let projection = self.projections.get(&TypeId::of::<T>())?.get(name)?;
+        Some(&**projection)
+    }
+
+    /// Get the projection of a `T` to a `U` registered as `name`, if any.
+    pub fn get<T, U>(&self, name: &str) -> Option<&BoxedProjection<'static, T, U>>
+    where
+        T: Any,
+        U: ?Sized + Pointee + Any,
+    {
+        self.get_any::<T>(name)?.downcast_ref()
+    }
+
+    /**
+       Apply the projection registered as `name` for the value owned by `owner`, or return
+       [`None`] if there is no projection to a `U` with this name.
+
+       # Panics
+       Like [`Projection::apply`], the registered getter must return a subregion of the owned
+       value.
+    */
+    #[track_caller]
+    pub fn resolve<P, U>(&self, owner: &P, name: &str) -> Option<Subptr<P, U>>
+    where
+        P: SharedOwner,
+        P::Target: Any + Sized,
+        U: ?Sized + Pointee + Any,
+    {
+        Some(self.get::<P::Target, U>(name)?.apply(owner))
+    }
+
+    /**
+       Reconstruct the projection identified by `key`, looking up its owner by id with `owner`,
+       see [`resolve`](ProjectionRegistry::resolve).
+    */
+    #[track_caller]
+    pub fn resolve_key<Id, P, U, F>(
+        &self,
+        key: &ProjectionKey<Id>,
+        owner: F,
+    ) -> Option<Subptr<P, U>>
+    where
+        P: SharedOwner,
+        P::Target: Any + Sized,
+        U: ?Sized + Pointee + Any,
+        F: FnOnce(&Id) -> Option<P>,
+    {
+        self.resolve(&owner(&key.owner)?, &key.name)
+    }
+}
+
+/**
+* A reference to a named projection of an owner identified by an `Id`, which can be persisted
+* (with the `serde` feature, as an `(owner, name)` pair) and reconstructed with
+* [`ProjectionRegistry::resolve_key`].
+*/
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProjectionKey<Id> {
+    /// The id of the owner.
+    pub owner: Id,
+    /// The name of the projection in the [`ProjectionRegistry`].
+    pub name: String,
+}
+
+impl<Id> ProjectionKey<Id> {
+    /// Create a [`ProjectionKey`] to the projection registered as `name` of the owner `owner`.
+    pub fn new(owner: Id, name: impl Into<String>) -> Self {
+        ProjectionKey {
+            owner,
+            name: name.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use crate::{ProjectionKey, ProjectionRegistry, Subarc, Subrc};
+
+    struct Config {
+        name: String,
+        ports: [u16; 2],
+    }
+
+    fn config(name: &str) -> Config {
+        Config {
+            name: String::from(name),
+            ports: [80, 443],
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = ProjectionRegistry::new();
+        registry.register("name", |config: &Config| &config.name);
+        registry.register("ports", |config: &Config| &config.ports[..]);
+        assert!(registry.contains::<Config>("name"));
+        assert!(!registry.contains::<Config>("url"));
+        assert!(!registry.contains::<String>("name"));
+
+        let rc = Rc::new(config("server"));
+        let ports: Subrc<Config, [u16]> = registry.resolve(&rc, "ports").unwrap();
+        assert!(std::ptr::eq(&*ports, &rc.ports[..]));
+        assert!(registry.resolve::<_, str>(&rc, "name").is_none());
+        assert!(registry.resolve::<_, String>(&rc, "url").is_none());
+
+        let arc = Arc::new(config("client"));
+        let name: Subarc<Config, String> = registry.resolve(&arc, "name").unwrap();
+        assert_eq!(*name, "client");
+    }
+
+    #[test]
+    fn test_resolve_key() {
+        let mut registry = ProjectionRegistry::new();
+        registry.register("name", |config: &Config| &config.name);
+
+        let owners = [Rc::new(config("first")), Rc::new(config("second"))];
+        let key = ProjectionKey::new(1, "name");
+        let name: Subrc<Config, String> = registry
+            .resolve_key(&key, |&id| owners.get(id).cloned())
+            .unwrap();
+        assert!(std::ptr::eq(&*name, &owners[1].name));
+
+        let key = ProjectionKey::new(2, "name");
+        let name = registry.resolve_key::<_, _, String, _>(&key, |&id| owners.get(id).cloned());
+        assert!(name.is_none());
+    }
+}
@@ -0,0 +1,141 @@
+use ::rkyv::{api::high::HighValidator, bytecheck::CheckBytes, rancor::Source, Portable};
+
+use crate::{
+    subptr::{expect_valid, get_offset},
+    Pointee, SharedOwner, Subptr,
+};
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner,
+    P::Target: AsRef<[u8]>,
+    U: Pointee + Portable,
+{
+    /**
+       Create a [`Subptr`] to the archived value `U` (an `Archived<T>`) at the root of the
+       `rkyv` archive held by the owned buffer, validating it with [`rkyv::access`].
+
+       The buffer may be an `AlignedVec`, as returned by `rkyv::to_bytes`, or an `[u8]` whose
+       storage is aligned enough for the archive (the data of an `Rc<[u8]>` is only aligned to
+       its reference counts). The archived value and its fields, projected to with
+       [`map_archived`](Subptr::map_archived), keep the buffer alive, so zero-copy views into an
+       archive can be shared without borrowing it.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           use rkyv::{rancor::Error, util::AlignedVec, Archive, Archived, Serialize};
+
+           #[derive(Archive, Serialize)]
+           struct Foo {
+               value: u32,
+               name: String,
+           }
+
+           let foo = Foo { value: 42, name: String::from("foo") };
+           let rc = Rc::new(rkyv::to_bytes::<Error>(&foo).unwrap());
+           let archived: Subrc<AlignedVec, Archived<Foo>> =
+               Subrc::project_archived::<Error>(&rc).unwrap();
+           let name = archived.map_archived(|foo| foo.name.as_str());
+           drop(rc);
+           assert_eq!(&*name, "foo");
+       ```
+    */
+    pub fn project_archived<E>(owner: &P) -> Result<Self, E>
+    where
+        U: for<'a> CheckBytes<HighValidator<'a, E>>,
+        E: Source,
+    {
+        let ptr = ::rkyv::access::<U, E>((**owner).as_ref())? as *const U;
+        // SAFETY: the archived value was borrowed from the owned value, which the clone of
+        // `owner` keeps alive and shared, see `new_indirect`.
+        Ok(unsafe { Subptr::from_raw_parts(owner.clone(), ptr) })
+    }
+
+    /**
+       Project this [`Subptr`] to a part of its archived target, like [`map`](Subptr::map), but
+       which may lie anywhere within the archive: the contents of an archived `String` or `Vec`
+       are reached through a relative pointer, outside of the archived value.
+
+       # Panics
+       `f` returning anything other than a reference into the archive held by the owned buffer
+       will result in a panic.
+    */
+    #[track_caller]
+    pub fn map_archived<V, F>(self, f: F) -> Subptr<P, V>
+    where
+        V: ?Sized + Pointee,
+        F: FnOnce(&U) -> &V,
+    {
+        let v = f(self.get());
+        let bytes = (**self.owner()).as_ref();
+        expect_valid(get_offset(bytes, v), bytes, v);
+        let ptr = v as *const V;
+        // SAFETY: the value lies within the owned buffer, which the owner keeps alive and shared.
+        unsafe { Subptr::from_raw_parts(Subptr::into_owner(self), ptr) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use ::rkyv::{rancor::Error, util::AlignedVec, Archive, Archived, Serialize};
+
+    use crate::{Subarc, Subrc};
+
+    #[derive(Archive, Serialize)]
+    struct Foo {
+        value: u32,
+        items: Vec<u16>,
+    }
+
+    fn foo() -> Foo {
+        Foo {
+            value: 42,
+            items: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn test_project_archived() {
+        let rc = Rc::new(::rkyv::to_bytes::<Error>(&foo()).unwrap());
+        let archived: Subrc<AlignedVec, Archived<Foo>> =
+            Subrc::project_archived::<Error>(&rc).unwrap();
+        let items = archived.clone().map_archived(|foo| foo.items.as_slice());
+        let value = archived.map(|foo| &foo.value);
+        assert!(rc.as_slice().as_ptr_range().contains(&items.as_ptr().cast::<u8>()));
+        assert_eq!(Rc::strong_count(&rc), 3);
+
+        drop(rc);
+        assert_eq!(*value, 42);
+        assert_eq!(items.iter().map(|item| item.to_native()).sum::<u16>(), 6);
+    }
+
+    #[test]
+    fn test_project_archived_slice() {
+        let bytes = ::rkyv::to_bytes::<Error>(&foo()).unwrap();
+        let arc: Arc<[u8]> = Arc::from(bytes.as_slice());
+        let archived: Subarc<[u8], Archived<Foo>> =
+            Subarc::project_archived::<Error>(&arc).unwrap();
+        std::thread::spawn(move || assert_eq!(archived.items.len(), 3))
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_for_map_archived_outside() {
+        static VALUE: u32 = 42;
+        let rc = Rc::new(::rkyv::to_bytes::<Error>(&foo()).unwrap());
+        let archived = Subrc::<_, Archived<Foo>>::project_archived::<Error>(&rc).unwrap();
+        archived.map_archived(|_| &VALUE);
+    }
+
+    #[test]
+    fn test_project_archived_invalid() {
+        let rc: Rc<[u8]> = Rc::from(&[0xffu8; 3][..]);
+        assert!(Subrc::<_, Archived<Foo>>::project_archived::<Error>(&rc).is_err());
+    }
+}
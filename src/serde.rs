@@ -0,0 +1,109 @@
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+use alloc::string::String;
+
+use crate::{Pointee, ProjectionKey, SharedOwner, Subptr};
+
+/**
+ Serializes the target, as if it was stored in place of the [`Subptr`].
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::Subrc;
+let subrc = Subrc::new(Rc::new((1u8, [2u8, 3])), |t| &t.1);
+assert_eq!(serde_json::to_string(&subrc).unwrap(), "[2,3]");
+```
+*/
+impl<P, U> Serialize for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+/**
+ Serializes the key as an `(owner, name)` pair.
+
+ # Example
+```rust
+# use subrc::ProjectionKey;
+let key = ProjectionKey::new(7, "db_url");
+let json = serde_json::to_string(&key).unwrap();
+assert_eq!(json, r#"[7,"db_url"]"#);
+assert_eq!(serde_json::from_str::<ProjectionKey<u32>>(&json).unwrap(), key);
+```
+*/
+impl<Id: Serialize> Serialize for ProjectionKey<Id> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (&self.owner, &self.name).serialize(serializer)
+    }
+}
+
+impl<'de, Id: Deserialize<'de>> Deserialize<'de> for ProjectionKey<Id> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (owner, name) = <(Id, String)>::deserialize(deserializer)?;
+        Ok(ProjectionKey { owner, name })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use ::serde::Serialize;
+
+    use crate::{ProjectionKey, ProjectionRegistry, Subarc, Subrc};
+
+    struct Config {
+        name: String,
+        ports: [u16; 2],
+    }
+
+    #[derive(Serialize)]
+    struct Response {
+        name: Subrc<Config, str>,
+        ports: Subrc<Config, [u16]>,
+    }
+
+    #[test]
+    fn test_serialize() {
+        let rc = Rc::new(Config {
+            name: String::from("server"),
+            ports: [80, 443],
+        });
+        let response = Response {
+            name: Subrc::new_indirect(rc.clone(), |config| config.name.as_str()),
+            ports: Subrc::new(rc, |config| &config.ports[..]),
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"name":"server","ports":[80,443]}"#
+        );
+
+        let subarc = Subarc::new(Arc::new((1u8, Some(2u8))), |t| &t.1);
+        assert_eq!(serde_json::to_string(&subarc).unwrap(), "2");
+    }
+
+    #[test]
+    fn test_projection_key_round_trip() {
+        let mut registry = ProjectionRegistry::new();
+        registry.register("ports", |config: &Config| &config.ports);
+
+        let keys = vec![ProjectionKey::new(String::from("main"), "ports")];
+        let json = serde_json::to_string(&keys).unwrap();
+        assert_eq!(json, r#"[["main","ports"]]"#);
+
+        let rc = Rc::new(Config {
+            name: String::from("main"),
+            ports: [80, 443],
+        });
+        let keys: Vec<ProjectionKey<String>> = serde_json::from_str(&json).unwrap();
+        let ports: Subrc<Config, [u16; 2]> = registry
+            .resolve_key(&keys[0], |id| (*id == rc.name).then(|| rc.clone()))
+            .unwrap();
+        assert!(std::ptr::eq(&*ports, &rc.ports));
+    }
+}
@@ -0,0 +1,484 @@
+use core::{
+    array,
+    iter::FusedIterator,
+    ops::{Deref, Index},
+    slice::SliceIndex,
+};
+
+use crate::{SharedOwner, Subptr};
+
+/**
+* A [`Subptr`] to a slice, which hands out projections to its elements and sub-slices.
+*
+* All the projections share the same owner, and are created without re-validating them, since
+* they are known to lie within the already validated slice.
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{Subrc, SubrcSlice};
+struct Foo {
+    items: [i32; 4],
+}
+
+let rc = Rc::new(Foo { items: [1, 2, 3, 4] });
+let items = SubrcSlice::from(Subrc::<_, [i32]>::new(rc.clone(), |foo| &foo.items));
+assert_eq!(items.len(), 4);
+assert_eq!(items[1], 2);
+
+let third: Subrc<Foo, i32> = items.get(2).unwrap();
+assert!(std::ptr::eq(&*third, &rc.items[2]));
+
+let (head, tail) = items.split_at(1);
+assert_eq!(&head[..], &[1]);
+assert_eq!(&tail[..], &[2, 3, 4]);
+```
+*/
+pub struct SubptrSlice<P, E> {
+    subptr: Subptr<P, [E]>,
+}
+
+impl<P, E> SubptrSlice<P, E>
+where
+    P: SharedOwner,
+{
+    /// Create a [`SubptrSlice`] from a [`Subptr`] to a slice.
+    pub fn new(subptr: Subptr<P, [E]>) -> Self {
+        SubptrSlice { subptr }
+    }
+
+    /// Convert back into a plain [`Subptr`] to the slice.
+    pub fn into_subptr(self) -> Subptr<P, [E]> {
+        self.subptr
+    }
+
+    /// Get the projected slice.
+    pub fn as_slice(&self) -> &[E] {
+        self.subptr.get()
+    }
+
+    /// Return the number of elements in the slice.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Return `true` if the slice has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Return a projection to the element at `index`, or [`None`] if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Subptr<P, E>> {
+        let element = self.as_slice().get(index)?;
+        // SAFETY: the element lies within the slice, which the owner keeps alive.
+        Some(unsafe { Subptr::from_raw_parts(self.subptr.owner().clone(), element) })
+    }
+
+    /// Return a projection to the elements in `start..end`.
+    fn range(&self, start: usize, end: usize) -> Self {
+        let range = &self.as_slice()[start..end];
+        // SAFETY: the sub-slice lies within the slice, which the owner keeps alive.
+        let subptr = unsafe { Subptr::from_raw_parts(self.subptr.owner().clone(), range) };
+        SubptrSlice { subptr }
+    }
+
+    /**
+       Divide the slice into two projections at `mid`, like [`slice::split_at`].
+
+       # Panics
+       Panics if `mid > len`.
+    */
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len(), "mid > len");
+        (self.range(0, mid), self.range(mid, self.len()))
+    }
+
+    /**
+       Return an iterator over projections to `chunk_size` elements of the slice at a time, like
+       [`slice::chunks`]. The last chunk may be shorter.
+
+       # Panics
+       Panics if `chunk_size` is 0.
+    */
+    pub fn chunks(&self, chunk_size: usize) -> Chunks<'_, P, E> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        Chunks {
+            slice: self,
+            start: 0,
+            chunk_size,
+        }
+    }
+
+    /// Return an iterator over projections to each element of the slice.
+    pub fn iter(&self) -> Iter<'_, P, E> {
+        Iter {
+            slice: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+}
+
+impl<P, E> Clone for SubptrSlice<P, E>
+where
+    P: SharedOwner,
+{
+    fn clone(&self) -> Self {
+        SubptrSlice {
+            subptr: self.subptr.clone(),
+        }
+    }
+}
+
+impl<P, E> From<Subptr<P, [E]>> for SubptrSlice<P, E>
+where
+    P: SharedOwner,
+{
+    fn from(subptr: Subptr<P, [E]>) -> Self {
+        Self::new(subptr)
+    }
+}
+
+impl<P, E> Deref for SubptrSlice<P, E>
+where
+    P: SharedOwner,
+{
+    type Target = [E];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<P, E, I> Index<I> for SubptrSlice<P, E>
+where
+    P: SharedOwner,
+    I: SliceIndex<[E]>,
+{
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &self.as_slice()[index]
+    }
+}
+
+impl<'a, P, E> IntoIterator for &'a SubptrSlice<P, E>
+where
+    P: SharedOwner,
+{
+    type Item = Subptr<P, E>;
+    type IntoIter = Iter<'a, P, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<P, E> IntoIterator for SubptrSlice<P, E>
+where
+    P: SharedOwner,
+{
+    type Item = Subptr<P, E>;
+    type IntoIter = Elements<P, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.subptr.iter_elements()
+    }
+}
+
+impl<P, E> Subptr<P, [E]>
+where
+    P: SharedOwner,
+{
+    /**
+       Return an iterator over projections to each element of the projected slice. Each of them
+       shares the owner, which the iterator keeps alive on its own.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           struct Foo {
+               items: [i32; 3],
+           }
+
+           let rc = Rc::new(Foo { items: [1, 2, 3] });
+           let items: Vec<Subrc<Foo, i32>> = Subrc::<_, [i32]>::new(rc.clone(), |foo| &foo.items)
+               .iter_elements()
+               .collect();
+           assert!(std::ptr::eq(&*items[1], &rc.items[1]));
+       ```
+    */
+    pub fn iter_elements(&self) -> Elements<P, E> {
+        Elements {
+            slice: self.clone(),
+            front: 0,
+            back: self.len(),
+        }
+    }
+}
+
+impl<P, E, const N: usize> Subptr<P, [E; N]>
+where
+    P: SharedOwner,
+{
+    /**
+       Return an iterator over projections to each element of the projected array, see
+       [`Subptr::iter_elements`](Subptr#method.iter_elements-1) for slices.
+    */
+    pub fn iter_elements(&self) -> Elements<P, E> {
+        let slice: &[E] = self.get();
+        Elements {
+            // SAFETY: the array is kept alive by its owner, of which this is a clone.
+            slice: unsafe { Subptr::from_raw_parts(self.owner().clone(), slice) },
+            front: 0,
+            back: N,
+        }
+    }
+
+    /**
+       Return an array of projections to each element of the projected array, like
+       [`array::each_ref`](https://doc.rust-lang.org/std/primitive.array.html#method.each_ref).
+
+       ## Example
+       ```rust
+           # use std::sync::Arc;
+           # use subrc::{subarc, Subarc};
+           struct Slots {
+               slots: [u64; 4],
+           }
+
+           let arc = Arc::new(Slots { slots: [0; 4] });
+           let [a, b, c, d]: [Subarc<Slots, u64>; 4] = subarc!(arc.slots).each_ref();
+           assert!(std::ptr::eq(&*c, &arc.slots[2]));
+       ```
+    */
+    pub fn each_ref(&self) -> [Subptr<P, E>; N] {
+        let elements = self.iter_elements();
+        array::from_fn(|index| elements.element(index))
+    }
+}
+
+/**
+ An owning iterator over projections to the elements of a slice or an array, see
+ [`Subptr::iter_elements`].
+*/
+pub struct Elements<P, E> {
+    slice: Subptr<P, [E]>,
+    front: usize,
+    back: usize,
+}
+
+impl<P, E> Elements<P, E>
+where
+    P: SharedOwner,
+{
+    fn element(&self, index: usize) -> Subptr<P, E> {
+        let element = &self.slice[index];
+        // SAFETY: the element lies within the slice, which the owner keeps alive.
+        unsafe { Subptr::from_raw_parts(self.slice.owner().clone(), element) }
+    }
+}
+
+impl<P, E> Iterator for Elements<P, E>
+where
+    P: SharedOwner,
+{
+    type Item = Subptr<P, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.element(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<P, E> DoubleEndedIterator for Elements<P, E>
+where
+    P: SharedOwner,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.element(self.back))
+    }
+}
+
+impl<P: SharedOwner, E> ExactSizeIterator for Elements<P, E> {}
+impl<P: SharedOwner, E> FusedIterator for Elements<P, E> {}
+
+/// An iterator over projections to the elements of a [`SubptrSlice`], see [`SubptrSlice::iter`].
+pub struct Iter<'a, P, E> {
+    slice: &'a SubptrSlice<P, E>,
+    front: usize,
+    back: usize,
+}
+
+impl<P, E> Iterator for Iter<'_, P, E>
+where
+    P: SharedOwner,
+{
+    type Item = Subptr<P, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.slice.get(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<P, E> DoubleEndedIterator for Iter<'_, P, E>
+where
+    P: SharedOwner,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.slice.get(self.back)
+    }
+}
+
+impl<P: SharedOwner, E> ExactSizeIterator for Iter<'_, P, E> {}
+impl<P: SharedOwner, E> FusedIterator for Iter<'_, P, E> {}
+
+/// An iterator over projections to chunks of a [`SubptrSlice`], see [`SubptrSlice::chunks`].
+pub struct Chunks<'a, P, E> {
+    slice: &'a SubptrSlice<P, E>,
+    start: usize,
+    chunk_size: usize,
+}
+
+impl<P, E> Iterator for Chunks<'_, P, E>
+where
+    P: SharedOwner,
+{
+    type Item = SubptrSlice<P, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.slice.len();
+        if self.start == len {
+            return None;
+        }
+        let end = len.min(self.start + self.chunk_size);
+        let chunk = self.slice.range(self.start, end);
+        self.start = end;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.slice.len() - self.start).div_ceil(self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl<P: SharedOwner, E> ExactSizeIterator for Chunks<'_, P, E> {}
+impl<P: SharedOwner, E> FusedIterator for Chunks<'_, P, E> {}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::{Subrc, SubrcSlice};
+
+    struct Foo {
+        _value: u8,
+        items: [u32; 5],
+    }
+
+    fn items() -> (Rc<Foo>, SubrcSlice<Foo, u32>) {
+        let rc = Rc::new(Foo {
+            _value: 0,
+            items: [1, 2, 3, 4, 5],
+        });
+        let items = Subrc::<_, [u32]>::new(rc.clone(), |foo| &foo.items).into();
+        (rc, items)
+    }
+
+    #[test]
+    fn test_get() {
+        let (rc, items) = items();
+        assert!(std::ptr::eq(&*items.get(4).unwrap(), &rc.items[4]));
+        assert!(items.get(5).is_none());
+        assert_eq!(items[0], 1);
+        assert_eq!(&items[1..3], &[2, 3]);
+    }
+
+    #[test]
+    fn test_split_at() {
+        let (rc, items) = items();
+        let (head, tail) = items.split_at(2);
+        assert!(std::ptr::eq(&*head, &rc.items[..2]));
+        assert!(std::ptr::eq(&*tail, &rc.items[2..]));
+
+        let (all, none) = items.split_at(5);
+        assert_eq!(all.len(), 5);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let (rc, items) = items();
+        let elements: Vec<_> = items.iter().collect();
+        assert_eq!(elements.len(), 5);
+        for (element, item) in elements.iter().zip(&rc.items) {
+            assert!(std::ptr::eq(&**element, item));
+        }
+        assert_eq!(*items.iter().next_back().unwrap(), 5);
+        assert_eq!(Rc::strong_count(&rc), 7);
+    }
+
+    #[test]
+    fn test_iter_elements() {
+        let (rc, items) = items();
+        let array = Subrc::new(rc.clone(), |foo| &foo.items);
+        let from_array: Vec<_> = array.iter_elements().rev().collect();
+        let from_slice: Vec<_> = items.into_iter().rev().collect();
+        drop(array);
+
+        assert_eq!(from_array.len(), 5);
+        for ((a, b), item) in from_array
+            .iter()
+            .zip(&from_slice)
+            .zip(rc.items.iter().rev())
+        {
+            assert!(std::ptr::eq(&**a, item));
+            assert!(std::ptr::eq(&**b, item));
+        }
+        assert_eq!(Rc::strong_count(&rc), 11);
+    }
+
+    #[test]
+    fn test_each_ref() {
+        let (rc, _items) = items();
+        let elements = Subrc::new(rc.clone(), |foo| &foo.items).each_ref();
+        for (element, item) in elements.iter().zip(&rc.items) {
+            assert!(std::ptr::eq(&**element, item));
+        }
+    }
+
+    #[test]
+    fn test_chunks() {
+        let (_rc, items) = items();
+        let chunks: Vec<_> = items.chunks(2).map(|chunk| chunk.to_vec()).collect();
+        assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(items.chunks(2).len(), 3);
+    }
+}
@@ -0,0 +1,348 @@
+use core::{borrow::Borrow, fmt, marker::PhantomData, ops::Deref};
+
+use crate::{
+    subptr::{expect_valid, get_offset},
+    Pointee, ProjectionError, SharedOwner, Subptr,
+};
+
+mod sealed {
+    /// Metadata which can be stored in a `u32` (or not at all) for a
+    /// [`SmallSubptr`](crate::SmallSubptr).
+    pub trait CompactMetadata: Copy {
+        type Compact: Copy;
+
+        fn compact(self) -> Option<Self::Compact>;
+
+        fn expand(compact: Self::Compact) -> Self;
+    }
+
+    impl CompactMetadata for () {
+        type Compact = ();
+
+        fn compact(self) -> Option<Self::Compact> {
+            Some(())
+        }
+
+        fn expand(_compact: Self::Compact) -> Self {}
+    }
+
+    impl CompactMetadata for usize {
+        type Compact = u32;
+
+        fn compact(self) -> Option<Self::Compact> {
+            u32::try_from(self).ok()
+        }
+
+        fn expand(compact: Self::Compact) -> Self {
+            compact as usize
+        }
+    }
+}
+
+use sealed::CompactMetadata;
+
+type Compact<U> = <<U as Pointee>::Metadata as CompactMetadata>::Compact;
+
+/**
+* A compact [`Subptr`], which stores the offset of its target within the owned value (and the
+* length of a slice or [`str`] target) as a `u32` instead of a pointer.
+*
+* A `SmallSubrc<T, [E]>` takes two words on 64-bit targets instead of three, at the cost of
+* recomputing the target pointer on every access. The target must be a sized type, a slice or a
+* [`str`] lying directly within the owned value (not behind an indirection), less than 4 GiB into
+* it, which is checked when the projection is created.
+*
+* # Example
+```rust
+# use std::{mem::size_of, rc::Rc};
+# use subrc::{SmallSubrc, Subrc};
+struct Packet {
+    header: [u8; 4],
+    payload: [u8; 12],
+}
+
+let rc = Rc::new(Packet { header: [1; 4], payload: [2; 12] });
+let payload: SmallSubrc<Packet, [u8]> = SmallSubrc::new(rc, |packet| &packet.payload[..]);
+assert_eq!(payload[0], 2);
+assert!(size_of::<SmallSubrc<Packet, [u8]>>() < size_of::<Subrc<Packet, [u8]>>());
+```
+*/
+pub struct SmallSubptr<P, U>
+where
+    U: ?Sized + Pointee,
+    U::Metadata: CompactMetadata,
+{
+    owner: P,
+    offset: u32,
+    metadata: Compact<U>,
+    // Invariant in `U` like `Subptr`, which it converts into.
+    _target: PhantomData<fn(U) -> U>,
+}
+
+// SAFETY: like a `Subptr`, a `SmallSubptr` behaves like its owner bundled with a shared reference
+// to the target.
+unsafe impl<P, U> Send for SmallSubptr<P, U>
+where
+    P: Send,
+    U: ?Sized + Pointee + Sync,
+    U::Metadata: CompactMetadata,
+{
+}
+unsafe impl<P, U> Sync for SmallSubptr<P, U>
+where
+    P: Sync,
+    U: ?Sized + Pointee + Sync,
+    U::Metadata: CompactMetadata,
+{
+}
+
+impl<P, U> SmallSubptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+    U::Metadata: CompactMetadata,
+{
+    /**
+       Create a [`SmallSubptr`] pointing to the subregion of the value owned by `owner` returned
+       by `getter`, like [`Subptr::new`].
+
+       # Panics
+       Like [`Subptr::new`], `getter` must return a subregion of the owned value. Moreover, its
+       offset and length must fit in a `u32`. Use [`try_new`](SmallSubptr::try_new) to handle
+       these cases gracefully.
+    */
+    #[track_caller]
+    pub fn new<F>(owner: P, getter: F) -> Self
+    where
+        F: FnOnce(&P::Target) -> &U,
+    {
+        let u = getter(&owner);
+        let compact = compact(&*owner, u);
+        expect_valid(compact.map(|(offset, _)| offset as usize), &*owner, u);
+        let (offset, metadata) = compact.unwrap();
+        Self::from_parts(owner, offset, metadata)
+    }
+
+    /**
+       Like [`new`](SmallSubptr::new), but returns a [`ProjectionError`] instead of panicking if
+       `getter` does not return a subregion of the owned value, or if it is too large to be stored
+       compactly.
+    */
+    pub fn try_new<F>(owner: P, getter: F) -> Result<Self, ProjectionError>
+    where
+        F: FnOnce(&P::Target) -> &U,
+    {
+        let (offset, metadata) = compact(&*owner, getter(&owner))?;
+        Ok(Self::from_parts(owner, offset, metadata))
+    }
+
+    fn from_parts(owner: P, offset: u32, metadata: Compact<U>) -> Self {
+        SmallSubptr {
+            owner,
+            offset,
+            metadata,
+            _target: PhantomData,
+        }
+    }
+
+    /// Get the owner of the projected value.
+    pub fn owner(&self) -> &P {
+        &self.owner
+    }
+
+    /// Convert into a plain [`Subptr`] to the same target.
+    pub fn into_subptr(self) -> Subptr<P, U> {
+        let ptr = self.get() as *const U;
+        // SAFETY: the target lies within the owned value, which the owner keeps alive.
+        unsafe { Subptr::from_raw_parts(self.owner, ptr) }
+    }
+
+    /// Get the projected value.
+    pub fn get(&self) -> &U {
+        let t_ptr = (&*self.owner as *const P::Target).cast::<u8>();
+        let metadata = CompactMetadata::expand(self.metadata);
+        // SAFETY: the target was checked to lie at `offset` within the owned value when the
+        // projection was created, and the owner keeps the owned value alive and in place.
+        unsafe { &*U::from_raw_parts(t_ptr.add(self.offset as usize), metadata) }
+    }
+}
+
+fn compact<T, U>(t: &T, u: &U) -> Result<(u32, Compact<U>), ProjectionError>
+where
+    T: ?Sized,
+    U: ?Sized + Pointee,
+    U::Metadata: CompactMetadata,
+{
+    let offset = get_offset(t, u)?;
+    let offset = u32::try_from(offset).map_err(|_| ProjectionError::TooLarge)?;
+    let metadata = u.metadata().compact().ok_or(ProjectionError::TooLarge)?;
+    Ok((offset, metadata))
+}
+
+impl<P, U> Clone for SmallSubptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+    U::Metadata: CompactMetadata,
+{
+    fn clone(&self) -> Self {
+        Self::from_parts(self.owner.clone(), self.offset, self.metadata)
+    }
+}
+
+/// Convert a [`Subptr`] to its compact form, or give it back if its target lies behind an
+/// indirection, or too far into the owned value.
+impl<P, U> TryFrom<Subptr<P, U>> for SmallSubptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+    U::Metadata: CompactMetadata,
+{
+    type Error = Subptr<P, U>;
+
+    fn try_from(subptr: Subptr<P, U>) -> Result<Self, Self::Error> {
+        match compact(&**subptr.owner(), subptr.get()) {
+            Ok((offset, metadata)) => Ok(Self::from_parts(
+                Subptr::into_owner(subptr),
+                offset,
+                metadata,
+            )),
+            Err(_) => Err(subptr),
+        }
+    }
+}
+
+impl<P, U> From<SmallSubptr<P, U>> for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+    U::Metadata: CompactMetadata,
+{
+    fn from(small: SmallSubptr<P, U>) -> Self {
+        small.into_subptr()
+    }
+}
+
+impl<P, U> Deref for SmallSubptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+    U::Metadata: CompactMetadata,
+{
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        self.get()
+    }
+}
+
+impl<P, U> Borrow<U> for SmallSubptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+    U::Metadata: CompactMetadata,
+{
+    fn borrow(&self) -> &U {
+        self.get()
+    }
+}
+
+impl<P, U> fmt::Debug for SmallSubptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + fmt::Debug,
+    U::Metadata: CompactMetadata,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.get(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use crate::{ProjectionError, SmallSubarc, SmallSubrc, Subrc};
+
+    struct Packet {
+        header: [u8; 4],
+        payload: [u8; 12],
+        name: String,
+    }
+
+    fn packet() -> Packet {
+        Packet {
+            header: [1; 4],
+            payload: [2; 12],
+            name: String::from("packet"),
+        }
+    }
+
+    #[test]
+    fn test_small_subrc() {
+        let rc = Rc::new(packet());
+        let payload = SmallSubrc::new(rc.clone(), |packet| &packet.payload[2..]);
+        assert!(std::ptr::eq(&*payload, &rc.payload[2..]));
+        assert!(std::ptr::eq(&*payload.clone(), &rc.payload[2..]));
+
+        let header = Subrc::from(SmallSubrc::new(rc.clone(), |packet| &packet.header));
+        assert!(std::ptr::eq(&*header, &rc.header));
+        assert_eq!(Rc::strong_count(&rc), 3);
+    }
+
+    #[test]
+    fn test_small_subarc() {
+        let arc = Arc::new(*b"hello world");
+        let world: SmallSubarc<_, [u8]> = SmallSubarc::new(arc.clone(), |s| &s[6..]);
+        std::thread::spawn(move || assert_eq!(&*world, b"world"))
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_small_subrc_conversions() {
+        let rc = Rc::new(packet());
+        let name = Subrc::new_indirect(rc.clone(), |packet| packet.name.as_str());
+        let name = SmallSubrc::try_from(name).err().unwrap();
+        assert_eq!(&*name, "packet");
+
+        let payload = Subrc::new(rc.clone(), |packet| &packet.payload[..]);
+        let payload = SmallSubrc::try_from(payload).ok().unwrap();
+        assert!(std::ptr::eq(&*payload, &rc.payload[..]));
+
+        assert!(SmallSubrc::try_new(rc, |packet| packet.name.as_str()).is_err());
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_too_large() {
+        #[repr(C)]
+        struct Units {
+            units: [(); 1 << 33],
+            byte: u8,
+        }
+
+        let rc = Rc::new(Units {
+            units: [(); 1 << 33],
+            byte: 0,
+        });
+        assert_eq!(rc.byte, 0);
+        assert_eq!(
+            SmallSubrc::try_new(rc, |units| &units.units[..]).err(),
+            Some(ProjectionError::TooLarge)
+        );
+    }
+
+    #[test]
+    #[cfg(all(target_pointer_width = "64", not(feature = "debug-validate")))]
+    fn test_small_size() {
+        use std::mem::size_of;
+
+        assert_eq!(
+            size_of::<SmallSubrc<Packet, [u8]>>(),
+            2 * size_of::<usize>()
+        );
+        assert_eq!(size_of::<SmallSubrc<Packet, u8>>(), 2 * size_of::<usize>());
+        assert_eq!(size_of::<Subrc<Packet, [u8]>>(), 3 * size_of::<usize>());
+    }
+}
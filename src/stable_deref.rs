@@ -0,0 +1,44 @@
+use stable_deref_trait::{CloneStableDeref, StableDeref};
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+// SAFETY: the target is kept in place by the owner for as long as the projection exists, wherever
+// the projection is moved to. Only methods taking `&mut self` (like `make_mut`) may point it to
+// another value, as they may for `Rc`.
+unsafe impl<P, U> StableDeref for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+}
+
+// SAFETY: a clone shares the same owner, and points to the same target.
+unsafe impl<P, U> CloneStableDeref for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{boxed::Box, rc::Rc};
+
+    use stable_deref_trait::CloneStableDeref;
+
+    use crate::Subrc;
+
+    fn target<T: CloneStableDeref>(pointer: &T) -> *const T::Target {
+        &**pointer
+    }
+
+    #[test]
+    fn test_stable_deref() {
+        let rc = Rc::new((1u8, String::from("two")));
+        let subrc: Subrc<_, str> = Subrc::new_indirect(rc.clone(), |t| t.1.as_str());
+        let ptr = target(&subrc);
+        let moved = Box::new(subrc);
+        assert!(std::ptr::eq(target(&*moved), ptr));
+        assert!(std::ptr::eq(target(&(*moved).clone()), ptr));
+    }
+}
@@ -0,0 +1,154 @@
+use core::{
+    borrow::Borrow,
+    fmt,
+    ops::{Deref, RangeBounds},
+};
+
+use crate::{SharedOwner, Subptr};
+
+/**
+* A [`Subptr`] to a [`str`], which hands out projections to its substrings.
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{Subrc, SubrcStr};
+let rc: Rc<str> = Rc::from("hello world");
+let s = SubrcStr::from(Subrc::whole(rc));
+
+let world = s.substr(6..).unwrap();
+assert_eq!(world.as_str(), "world");
+assert_eq!(format!("{}!", world), "world!");
+
+// ranges must lie on char boundaries
+let rc: Rc<str> = Rc::from("héllo");
+assert!(SubrcStr::from(Subrc::whole(rc)).substr(2..).is_none());
+```
+*/
+pub struct SubptrStr<P> {
+    subptr: Subptr<P, str>,
+}
+
+impl<P> SubptrStr<P>
+where
+    P: SharedOwner,
+{
+    /// Create a [`SubptrStr`] from a [`Subptr`] to a [`str`].
+    pub fn new(subptr: Subptr<P, str>) -> Self {
+        SubptrStr { subptr }
+    }
+
+    /// Convert back into a plain [`Subptr`] to the [`str`].
+    pub fn into_subptr(self) -> Subptr<P, str> {
+        self.subptr
+    }
+
+    /// Get the projected string slice.
+    pub fn as_str(&self) -> &str {
+        self.subptr.get()
+    }
+
+    /**
+       Return a projection to the substring in `range`, or [`None`] if it is out of bounds or does
+       not lie on char boundaries.
+    */
+    pub fn substr<R>(&self, range: R) -> Option<Self>
+    where
+        R: RangeBounds<usize>,
+    {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        let substr = self.as_str().get(bounds)?;
+        // SAFETY: the substring lies within the string, which the owner keeps alive.
+        let subptr = unsafe { Subptr::from_raw_parts(self.subptr.owner().clone(), substr) };
+        Some(SubptrStr { subptr })
+    }
+}
+
+impl<P> Clone for SubptrStr<P>
+where
+    P: SharedOwner,
+{
+    fn clone(&self) -> Self {
+        SubptrStr {
+            subptr: self.subptr.clone(),
+        }
+    }
+}
+
+impl<P> From<Subptr<P, str>> for SubptrStr<P>
+where
+    P: SharedOwner,
+{
+    fn from(subptr: Subptr<P, str>) -> Self {
+        Self::new(subptr)
+    }
+}
+
+impl<P> Deref for SubptrStr<P>
+where
+    P: SharedOwner,
+{
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<P> Borrow<str> for SubptrStr<P>
+where
+    P: SharedOwner,
+{
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<P> fmt::Display for SubptrStr<P>
+where
+    P: SharedOwner,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Borrow, rc::Rc};
+
+    use crate::{Subrc, SubrcStr};
+
+    fn hello() -> (Rc<str>, SubrcStr<str>) {
+        let rc: Rc<str> = Rc::from("hello, wörld");
+        let s = Subrc::whole(rc.clone()).into();
+        (rc, s)
+    }
+
+    #[test]
+    fn test_substr() {
+        let (rc, s) = hello();
+        let world = s.substr(7..).unwrap();
+        assert!(std::ptr::eq(world.as_str(), &rc[7..]));
+
+        let orl = world.substr(1..=4).unwrap();
+        assert_eq!(orl.as_str(), "örl");
+        assert_eq!(Rc::strong_count(&rc), 4);
+    }
+
+    #[test]
+    fn test_substr_out_of_bounds_or_boundary() {
+        let (_rc, s) = hello();
+        assert!(s.substr(..14).is_none());
+        assert!(s.substr(9..).is_none());
+    }
+
+    #[test]
+    fn test_str_traits() {
+        let (_rc, s) = hello();
+        let borrowed: &str = s.borrow();
+        assert_eq!(borrowed, "hello, wörld");
+        assert_eq!(s.to_uppercase(), "HELLO, WÖRLD");
+        assert_eq!(format!("{:>14}", s.clone()), "  hello, wörld");
+    }
+}
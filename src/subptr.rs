@@ -0,0 +1,2131 @@
+#[cfg(doc)]
+use alloc::{boxed::Box, string::String, vec::Vec};
+use alloc::{rc::Rc, sync::Arc};
+use core::{
+    any::type_name,
+    borrow::Borrow,
+    convert::Infallible,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, RangeBounds},
+    ptr::{self, NonNull},
+};
+
+use crate::{NotUnique, Pointee, ProjectionError, SharedOwner};
+
+/**
+* A reference counted pointer to a sub-region (member) of the value owned by a [`SharedOwner`].
+*
+* You would usually use it through one of its aliases, [`Subrc`](crate::Subrc) or
+* [`Subarc`](crate::Subarc), but generic code can accept any `Subptr<P, U>`.
+*
+* The projected `U` may also be unsized (a slice, a [`str`] or a trait object), see [`Pointee`].
+* So may the owned value, e.g. a `Subrc<[T], T>` points to an element of an `Rc<[T]>`.
+*
+* The target usually lies within the owned value, which [`new`](Subptr::new) checks, but it may
+* also live behind an indirection the owned value keeps alive, such as a [`Box`], [`Vec`] or
+* [`String`] field, see [`new_indirect`](Subptr::new_indirect).
+*
+* A `Subptr` is covariant in its owner `P`, but unlike a shared reference it is invariant in `U`:
+* it can give mutable access to the target (see [`get_mut`](Subptr::get_mut)), whose type the
+* owned value still spells out. A `Subrc<T, &'static X>` can't be used as a `Subrc<T, &'a X>`,
+* or a shorter-lived reference could be written into the owned value:
+```compile_fail
+# use subrc::Subrc;
+type Owned = (&'static str, u8);
+
+fn shorten<'a>(subrc: Subrc<Owned, &'static str>) -> Subrc<Owned, &'a str> {
+    subrc
+}
+```
+* Dropping a `Subptr` only drops its owner.
+*
+* Neither the owner nor the target pointer can be null, so `Option<Subrc<T, U>>` is the same size
+* as `Subrc<T, U>`.
+*
+* # Example
+```rust
+# use std::{rc::Rc, sync::Arc};
+# use subrc::{SharedOwner, Subptr};
+struct Foo {
+    value: i32,
+}
+
+fn value_of<P: SharedOwner<Target = Foo>>(subptr: Subptr<P, i32>) -> i32 {
+    *subptr
+}
+
+let rc = Rc::new(Foo { value: 42 });
+assert_eq!(value_of(Subptr::new(rc, |foo| &foo.value)), 42);
+
+let arc = Arc::new(Foo { value: 24 });
+assert_eq!(value_of(Subptr::new(arc, |foo| &foo.value)), 24);
+```
+*/
+pub struct Subptr<P, U: ?Sized + Pointee> {
+    owner: P,
+    ptr: NonNull<U>,
+    // A `Subptr` must be invariant in `U`, as it can hand out a `&mut U` (see `get_mut`) to a
+    // target whose type the owner still knows at its original lifetimes.
+    _invariant: PhantomData<fn(U) -> U>,
+    // The offset of the target within the owned value when the projection was created, or `None`
+    // if it lies behind an indirection, for `get` to check against.
+    #[cfg(feature = "debug-validate")]
+    offset: Option<usize>,
+}
+
+// SAFETY: a `Subptr` behaves like its owner bundled with a shared reference to the target.
+unsafe impl<P: Send, U: ?Sized + Pointee + Sync> Send for Subptr<P, U> {}
+unsafe impl<P: Sync, U: ?Sized + Pointee + Sync> Sync for Subptr<P, U> {}
+
+/**
+ Like an [`Rc`], a [`Subptr`] coerces to a [`Subptr`] to an unsized target, e.g. from
+ `Subrc<T, [U; N]>` to `Subrc<T, [U]>`, or from `Subrc<T, U>` to `Subrc<T, dyn Trait>`.
+
+ Only available with the `nightly` feature. Unlike [`Rc`], a [`Subptr`] can't be the receiver of
+ a trait object method (`DispatchFromDyn`), since it holds its owner alongside the pointer.
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::Subrc;
+let subrc = Subrc::new(Rc::new((1u8, [2u8, 3])), |t| &t.1);
+let slice: Subrc<(u8, [u8; 2]), [u8]> = subrc;
+assert_eq!(&*slice, [2, 3]);
+```
+*/
+#[cfg(feature = "nightly")]
+impl<P, U, V> core::ops::CoerceUnsized<Subptr<P, V>> for Subptr<P, U>
+where
+    U: ?Sized + Pointee + core::marker::Unsize<V>,
+    V: ?Sized + Pointee,
+{
+}
+
+/// Cloning a [`Subptr`] only clones its owner, so the target doesn't need to be [`Clone`].
+impl<P: Clone, U: ?Sized + Pointee> Clone for Subptr<P, U> {
+    fn clone(&self) -> Self {
+        Subptr {
+            owner: self.owner.clone(),
+            ptr: self.ptr,
+            _invariant: PhantomData,
+            #[cfg(feature = "debug-validate")]
+            offset: self.offset,
+        }
+    }
+}
+
+pub(crate) fn get_offset<T: ?Sized, U: ?Sized>(t: &T, u: &U) -> Result<usize, ProjectionError> {
+    // Only the addresses are compared, so the pointers don't need to share their provenance.
+    let t_ptr = (t as *const T).addr();
+    let u_ptr = (u as *const U).addr();
+    check_offset(
+        (t_ptr, mem::size_of_val(t)),
+        (u_ptr, mem::size_of_val(u)),
+        mem::align_of_val(u),
+    )
+}
+
+/// Check that the `(address, size)` region `u` lies entirely within `t`, and is aligned to
+/// `align`, returning its offset within `t`. A zero-sized `u` may also lie right at the end of `t`,
+/// like an empty subslice at the end of a slice, or a field of a zero-sized `t` does.
+pub(crate) fn check_offset(
+    t: (usize, usize),
+    u: (usize, usize),
+    align: usize,
+) -> Result<usize, ProjectionError> {
+    let ((t_ptr, t_size), (u_ptr, u_size)) = (t, u);
+    if u_ptr < t_ptr {
+        return Err(ProjectionError::BeforeOwner);
+    }
+
+    let offset = u_ptr - t_ptr;
+    if offset > t_size || u_size > t_size - offset {
+        return Err(ProjectionError::OutOfBounds);
+    }
+
+    if !u_ptr.is_multiple_of(align) {
+        return Err(ProjectionError::Misaligned);
+    }
+
+    Ok(offset)
+}
+
+/// Panic if `offset` is an error, reporting where `u` is relative to the owned value `t`.
+#[track_caller]
+pub(crate) fn expect_valid<T: ?Sized, U: ?Sized>(
+    offset: Result<usize, ProjectionError>,
+    t: &T,
+    u: &U,
+) {
+    if let Err(e) = offset {
+        let u = ((u as *const U).addr(), mem::size_of_val(u));
+        invalid_projection::<T, U>(e, t, u);
+    }
+}
+
+/// Panic because the `(address, size)` region `u` is not a valid projection of `t`, reporting the
+/// caller of the constructor, the types involved, and the regions they span.
+#[cold]
+#[track_caller]
+pub(crate) fn invalid_projection<T: ?Sized, U: ?Sized>(
+    error: ProjectionError,
+    t: &T,
+    u: (usize, usize),
+) -> ! {
+    invalid_projection_to(error, t, type_name::<U>(), u)
+}
+
+/// Like [`invalid_projection`], with the name of the target type given at runtime, for callers
+/// which only report the error once the target type is out of reach.
+#[cold]
+#[track_caller]
+pub(crate) fn invalid_projection_to<T: ?Sized>(
+    error: ProjectionError,
+    t: &T,
+    u_type: &str,
+    u: (usize, usize),
+) -> ! {
+    let (t_ptr, t_size) = ((t as *const T).addr(), mem::size_of_val(t));
+    let (u_ptr, u_size) = u;
+    panic!(
+        "getter did not return portion of the object: {} (a `{}` at {:#x}..{:#x}, at offset {} \
+         from the owned `{}` at {:#x}..{:#x})",
+        error,
+        u_type,
+        u_ptr,
+        u_ptr.wrapping_add(u_size),
+        u_ptr.wrapping_sub(t_ptr) as isize,
+        type_name::<T>(),
+        t_ptr,
+        t_ptr + t_size,
+    );
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    /**
+       Create a [`Subptr`] pointer, which points to a subregion of the value owned by `owner`.
+       The `getter` function is used to specify the subregion. It must return a reference to a subregion
+       of the owned value. Returning anything else will result in a panic.
+
+       It is recommended to use the [`subrc`](crate::subrc) macro for better readability and an extra
+       safe guard to prevent panicking (by accidentally referencing something unrelated).
+
+       To project a field as a trait object, name the target type explicitly so the reference returned
+       by `getter` gets coerced, e.g. `Subrc::<_, dyn Any>::new(rc, |t| &t.field)`.
+
+       Zero-sized targets, such as marker fields or empty slices, may lie anywhere within the owned
+       value or right at its end, which is also where the fields of a zero-sized owned value are.
+
+       # Panics
+       In the `getter` function, returning anything other than a reference to a subregion of the owned
+       value will result in a panic. Use [`try_new`](Subptr::try_new) to handle this case gracefully.
+
+       ## Example
+       ```rust,should_panic
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let s = String::from("hello");
+           let rc = Rc::new(s);
+           let subrc = Subrc::new(rc.clone(), |s| &123);   // panic here: `123` is totally unrelated to `s`!
+       ```
+    */
+    #[track_caller]
+    pub fn new<F>(owner: P, getter: F) -> Self
+    where
+        F: FnOnce(&P::Target) -> &U,
+    {
+        let u = getter(&owner);
+        expect_valid(get_offset(&*owner, u), &*owner, u);
+        let ptr = u as *const U;
+        unsafe { Self::from_raw_parts(owner, ptr) }
+    }
+
+    /**
+       Like [`new`](Subptr::new), but returns a [`ProjectionError`] instead of panicking if `getter`
+       does not return a reference to a subregion of the owned value.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new(String::from("hello"));
+           assert!(Subrc::try_new(rc.clone(), |s| s).is_ok());
+           // `123` is totally unrelated to `s`
+           assert!(Subrc::try_new(rc, |_| &123).is_err());
+       ```
+    */
+    pub fn try_new<F>(owner: P, getter: F) -> Result<Self, ProjectionError>
+    where
+        F: FnOnce(&P::Target) -> &U,
+    {
+        let u = getter(&owner);
+        get_offset(&*owner, u)?;
+        let ptr = u as *const U;
+        Ok(unsafe { Self::from_raw_parts(owner, ptr) })
+    }
+
+    /**
+       Like [`new`](Subptr::new), but for subregions which might not be present, e.g. the content of
+       an enum variant: returns [`None`] if `getter` does.
+
+       # Panics
+       Like [`new`](Subptr::new), `getter` returning anything other than a reference to a subregion
+       of the owned value will result in a panic.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           enum Shape {
+               Circle(f64),
+               Square(f64),
+           }
+
+           let rc = Rc::new(Shape::Circle(1.0));
+           let radius = Subrc::try_new_opt(rc.clone(), |shape| match shape {
+               Shape::Circle(radius) => Some(radius),
+               _ => None,
+           });
+           assert_eq!(radius.as_deref(), Some(&1.0));
+
+           let side = Subrc::try_new_opt(rc, |shape| match shape {
+               Shape::Square(side) => Some(side),
+               _ => None,
+           });
+           assert!(side.is_none());
+       ```
+    */
+    #[track_caller]
+    pub fn try_new_opt<F>(owner: P, getter: F) -> Option<Self>
+    where
+        F: FnOnce(&P::Target) -> Option<&U>,
+    {
+        Self::try_new_with(owner, |t| getter(t).ok_or(())).ok()
+    }
+
+    /**
+       Like [`new`](Subptr::new), but for getters which can fail, e.g. for domain reasons:
+       propagates the error if `getter` returns one.
+
+       # Panics
+       Like [`new`](Subptr::new), `getter` returning anything other than a reference to a subregion
+       of the owned value will result in a panic.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           struct Config {
+               cache_size: Option<usize>,
+           }
+
+           let rc = Rc::new(Config { cache_size: None });
+           let cache_size = Subrc::try_new_with(rc, |config| {
+               config.cache_size.as_ref().ok_or("the cache is disabled")
+           });
+           assert_eq!(cache_size.err(), Some("the cache is disabled"));
+       ```
+    */
+    #[track_caller]
+    pub fn try_new_with<E, F>(owner: P, getter: F) -> Result<Self, E>
+    where
+        F: FnOnce(&P::Target) -> Result<&U, E>,
+    {
+        let u = getter(&owner)?;
+        expect_valid(get_offset(&*owner, u), &*owner, u);
+        let ptr = u as *const U;
+        Ok(unsafe { Self::from_raw_parts(owner, ptr) })
+    }
+
+    /**
+       Create a [`Subptr`] pointer like [`new`](Subptr::new), but without validating the reference
+       returned by `getter`.
+
+       # Safety
+       `getter` must return a reference to a subregion of the value it is given, i.e. the value owned
+       by `owner`. A plain (possibly nested) field access such as `|t| &t.a.b` always satisfies this.
+       Returning anything else is undefined behavior when the [`Subptr`] is dereferenced.
+
+       The check is still performed as a debug assertion.
+    */
+    #[track_caller]
+    pub unsafe fn new_unchecked<F>(owner: P, getter: F) -> Self
+    where
+        F: FnOnce(&P::Target) -> &U,
+    {
+        let u = getter(&owner);
+        #[cfg(debug_assertions)]
+        expect_valid(get_offset(&*owner, u), &*owner, u);
+        let ptr = u as *const U;
+        Self::from_raw_parts(owner, ptr)
+    }
+
+    /**
+       Create a [`Subptr`] pointer from a reference obtained from the value owned by `owner`
+       earlier, e.g. the result of a search through it.
+
+       Returns a [`ProjectionError`] if `u` is not a subregion of the owned value.
+
+       # Safety
+       `u` must have been borrowed from the owned value through shared borrows only, not through
+       a guard of some interior mutability, like the [`Ref`](core::cell::Ref) returned by
+       [`RefCell::borrow`](core::cell::RefCell::borrow): the owned value could be mutated again
+       once the guard is dropped, while the [`Subptr`] still points into it.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           struct Entry {
+               key: u32,
+               value: String,
+           }
+
+           let rc: Rc<[Entry]> = Rc::from([
+               Entry { key: 1, value: String::from("one") },
+               Entry { key: 2, value: String::from("two") },
+           ]);
+           let found = rc.iter().find(|entry| entry.key == 2).unwrap();
+           let entry = unsafe { Subrc::from_ref(rc.clone(), found) }.unwrap();
+           assert_eq!(entry.value, "two");
+       ```
+    */
+    pub unsafe fn from_ref(owner: P, u: &U) -> Result<Self, ProjectionError> {
+        get_offset(&*owner, u)?;
+        Ok(Self::from_raw_parts(owner, u))
+    }
+
+    /**
+       Create a [`Subptr`] pointer to a value the owned value keeps alive through an indirection,
+       e.g. the content of a [`Box`], an element of a [`Vec`] or a [`String`] field.
+
+       Such a value lies outside of the owned value, so unlike [`new`](Subptr::new), the reference
+       returned by `getter` is not checked to be a subregion of it. It stays valid anyway, since
+       the owned value can't be mutated (nor dropped) while it is shared with the [`Subptr`].
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           struct Foo {
+               name: String,
+               items: Vec<u32>,
+           }
+
+           let rc = Rc::new(Foo { name: String::from("foo"), items: vec![1, 2, 3] });
+           let name: Subrc<Foo, str> = Subrc::new_indirect(rc.clone(), |foo| foo.name.as_str());
+           assert_eq!(&*name, "foo");
+
+           let item = Subrc::new_indirect(rc.clone(), |foo| &foo.items[1]);
+           assert!(std::ptr::eq(&*item, &rc.items[1]));
+       ```
+    */
+    pub fn new_indirect<F>(owner: P, getter: F) -> Self
+    where
+        F: FnOnce(&P::Target) -> &U,
+    {
+        let ptr = getter(&owner) as *const U;
+        // SAFETY: the reference was borrowed from the owned value, which `owner` keeps alive and
+        // shared, so everything it keeps alive stays in place as well.
+        unsafe { Self::from_raw_parts(owner, ptr) }
+    }
+
+    /**
+       Project this [`Subptr`] further down, to a subregion of its current target. The returned
+       [`Subptr`] still owns the original value.
+
+       # Panics
+       `f` returning anything other than a reference to a subregion of the owned value or of the
+       current target will result in a panic.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               bar: Bar,
+           }
+
+           struct Bar {
+               value: i32,
+           }
+
+           let rc = Rc::new(Foo { bar: Bar { value: 42 } });
+           let bar: Subrc<Foo, Bar> = subrc!(rc.bar);
+           let value: Subrc<Foo, i32> = bar.map(|bar| &bar.value);
+           assert!(std::ptr::eq(&*value, &rc.bar.value));
+       ```
+    */
+    #[track_caller]
+    pub fn map<V, F>(self, f: F) -> Subptr<P, V>
+    where
+        V: ?Sized + Pointee,
+        F: FnOnce(&U) -> &V,
+    {
+        match self.try_map_with(|u| Ok::<_, Infallible>(f(u))) {
+            Ok(v) => v,
+            Err(never) => match never {},
+        }
+    }
+
+    /**
+       Like [`map`](Subptr::map), but for subregions which might not be present: returns [`None`]
+       if `f` does.
+
+       # Panics
+       Like [`new`](Subptr::new), `f` returning anything other than a reference to a subregion of the
+       owned value will result in a panic.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               bar: Option<i32>,
+           }
+
+           let rc = Rc::new(Foo { bar: Some(42) });
+           let bar = subrc!(rc.bar).try_map(|bar| bar.as_ref()).unwrap();
+           assert_eq!(*bar, 42);
+       ```
+    */
+    #[track_caller]
+    pub fn try_map<V, F>(self, f: F) -> Option<Subptr<P, V>>
+    where
+        V: ?Sized + Pointee,
+        F: FnOnce(&U) -> Option<&V>,
+    {
+        self.try_map_with(|u| f(u).ok_or(())).ok()
+    }
+
+    /**
+       Like [`map`](Subptr::map), but for fallible projections: propagates the error if `f` returns
+       one.
+
+       # Panics
+       Like [`new`](Subptr::new), `f` returning anything other than a reference to a subregion of the
+       owned value will result in a panic.
+    */
+    #[track_caller]
+    pub fn try_map_with<V, E, F>(self, f: F) -> Result<Subptr<P, V>, E>
+    where
+        V: ?Sized + Pointee,
+        F: FnOnce(&U) -> Result<&V, E>,
+    {
+        let u = self.get();
+        let v = f(u)?;
+        // A target reached through an indirection lies outside of the owned value, but its own
+        // subregions are valid too.
+        let offset = get_offset(&*self.owner, v).or_else(|e| get_offset(u, v).map_err(|_| e));
+        expect_valid(offset, &*self.owner, v);
+        let ptr = v as *const V;
+        Ok(unsafe { Subptr::from_raw_parts(self.owner, ptr) })
+    }
+
+    /**
+       Split this [`Subptr`] into two projections to subregions of its current target, like
+       [`Ref::map_split`](core::cell::Ref::map_split). Each of them holds its own clone of the
+       owner.
+
+       # Panics
+       Like [`map`](Subptr::map), `f` returning anything other than references to subregions of
+       the owned value or of the current target will result in a panic.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               pair: Pair,
+           }
+
+           struct Pair {
+               left: i32,
+               right: String,
+           }
+
+           let rc = Rc::new(Foo { pair: Pair { left: 1, right: String::from("two") } });
+           let (left, right): (Subrc<Foo, i32>, Subrc<Foo, String>) =
+               subrc!(rc.pair).map_split(|pair| (&pair.left, &pair.right));
+           assert!(std::ptr::eq(&*left, &rc.pair.left));
+           assert!(std::ptr::eq(&*right, &rc.pair.right));
+           assert_eq!(Rc::strong_count(&rc), 3);
+       ```
+    */
+    #[track_caller]
+    pub fn map_split<L, R, F>(self, f: F) -> (Subptr<P, L>, Subptr<P, R>)
+    where
+        L: ?Sized + Pointee,
+        R: ?Sized + Pointee,
+        F: FnOnce(&U) -> (&L, &R),
+    {
+        let u = self.get();
+        let (l, r) = f(u);
+        let offset = get_offset(&*self.owner, l).or_else(|e| get_offset(u, l).map_err(|_| e));
+        expect_valid(offset, &*self.owner, l);
+        let offset = get_offset(&*self.owner, r).or_else(|e| get_offset(u, r).map_err(|_| e));
+        expect_valid(offset, &*self.owner, r);
+        let (l, r) = (l as *const L, r as *const R);
+        // SAFETY: both targets were checked to lie within the owned value, or within the current
+        // target, which the owner keeps alive.
+        unsafe {
+            (
+                Subptr::from_raw_parts(self.owner.clone(), l),
+                Subptr::from_raw_parts(self.owner, r),
+            )
+        }
+    }
+
+    /**
+       Decompose a [`Subptr`] into its owner and a raw pointer to its target.
+
+       The parts can be reassembled with [`from_raw_parts`](Subptr::from_raw_parts).
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               value: i32,
+           }
+
+           let rc = Rc::new(Foo { value: 42 });
+           let (owner, ptr) = Subrc::into_raw_parts(subrc!(rc.value));
+           assert!(Rc::ptr_eq(&owner, &rc));
+           assert!(std::ptr::eq(ptr, &rc.value));
+
+           let subrc: Subrc<Foo, i32> = unsafe { Subrc::from_raw_parts(owner, ptr) };
+           assert!(std::ptr::eq(&*subrc, &rc.value));
+       ```
+    */
+    pub fn into_raw_parts(this: Self) -> (P, *const U) {
+        (this.owner, this.ptr.as_ptr())
+    }
+
+    /**
+       Discard the projection and return its owner, e.g. when broader access to the owned value
+       is needed after all.
+
+       This is an associated function, so it doesn't shadow an `into_owner` method of the target.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new((1u8, 2u32));
+           let owner = Subrc::into_owner(Subrc::new(rc.clone(), |t| &t.1));
+           assert!(Rc::ptr_eq(&owner, &rc));
+           assert_eq!(Rc::strong_count(&rc), 2);
+       ```
+    */
+    pub fn into_owner(this: Self) -> P {
+        this.owner
+    }
+
+    /**
+       Return the owned value if this is its only owner, discarding the projection, like
+       [`Rc::try_unwrap`](alloc::rc::Rc::try_unwrap).
+
+       Returns the projection back otherwise.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new((1u8, 2u32));
+           let subrc = Subrc::new(rc.clone(), |t| &t.1);
+           let subrc = Subrc::try_unwrap_owner(subrc).unwrap_err();
+
+           drop(rc);
+           assert_eq!(Subrc::try_unwrap_owner(subrc).ok(), Some((1, 2)));
+       ```
+    */
+    pub fn try_unwrap_owner(this: Self) -> Result<P::Target, Self>
+    where
+        P::Target: Sized,
+    {
+        let ptr = this.ptr.as_ptr();
+        // SAFETY: the target is kept alive by the same owner, which is handed back.
+        P::try_unwrap(this.owner).map_err(|owner| unsafe { Self::from_raw_parts(owner, ptr) })
+    }
+
+    /**
+       Leak the owner, so that the owned value is never dropped, and return a reference to the
+       target which lives for the rest of the program, like [`Box::leak`].
+
+       ## Example
+       ```rust
+           # use std::sync::Arc;
+           # use subrc::Subarc;
+           struct Config {
+               name: String,
+           }
+
+           let config = Arc::new(Config { name: String::from("app") });
+           let name: &'static str = Subarc::leak(Subarc::new_indirect(config, |c| c.name.as_str()));
+           assert_eq!(name, "app");
+       ```
+    */
+    pub fn leak(this: Self) -> &'static U
+    where
+        P: 'static,
+    {
+        let ptr = this.ptr;
+        mem::forget(this.owner);
+        // SAFETY: the owner is never dropped, and nor is the value it owns, which may only borrow
+        // `'static` data.
+        unsafe { ptr.as_ref() }
+    }
+
+    /**
+       Reassemble a [`Subptr`] from the parts returned by [`into_raw_parts`](Subptr::into_raw_parts).
+
+       # Safety
+       `ptr` must point to a valid `U` which stays alive and unmodified for as long as the value
+       owned by `owner` does, e.g. a subregion of that value. This holds for parts returned by
+       [`into_raw_parts`](Subptr::into_raw_parts), as long as `owner` still points to the same
+       allocation (e.g. it is the returned owner, or a clone of it).
+    */
+    pub unsafe fn from_raw_parts(owner: P, ptr: *const U) -> Self {
+        Subptr {
+            #[cfg(feature = "debug-validate")]
+            offset: get_offset(&*owner, &*ptr).ok(),
+            owner,
+            // SAFETY: `ptr` points to a valid `U`, so it isn't null.
+            ptr: NonNull::new_unchecked(ptr as *mut U),
+            _invariant: PhantomData,
+        }
+    }
+
+    /**
+       Get a mutable reference to the target, if this is the only owner of the owned value (there
+       are no other strong nor weak pointers to it), like [`Rc::get_mut`](alloc::rc::Rc::get_mut).
+
+       Returns [`None`] otherwise, or if the target lives behind an indirection (see
+       [`new_indirect`](Subptr::new_indirect)), which the owner can't give mutable access to.
+
+       This is an associated function, so it doesn't shadow a `get_mut` method of the target.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               value: i32,
+           }
+
+           let rc = Rc::new(Foo { value: 42 });
+           let mut subrc = subrc!(rc.value);
+           assert!(Subrc::get_mut(&mut subrc).is_none());
+
+           drop(rc);
+           *Subrc::get_mut(&mut subrc).unwrap() += 1;
+           assert_eq!(*subrc, 43);
+       ```
+    */
+    pub fn get_mut(this: &mut Self) -> Option<&mut U> {
+        let offset = get_offset(&*this.owner, this.get()).ok()?;
+        let metadata = Pointee::metadata(this.get());
+        let t_ptr = P::get_mut(&mut this.owner)? as *mut P::Target as *mut u8;
+        // SAFETY: the target lies at `offset` within the owned value, which we have unique access
+        // to. The pointer is derived from that unique access, so it is kept for later reads.
+        unsafe {
+            this.ptr =
+                NonNull::new_unchecked(U::from_raw_parts(t_ptr.add(offset), metadata) as *mut U);
+            Some(this.ptr.as_mut())
+        }
+    }
+
+    /**
+       Call `f` with a mutable reference to the target, if this is the only owner of the owned
+       value, see [`get_mut`](Subptr::get_mut). The reference can't escape `f`.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, NotUnique, Subrc};
+           struct Foo {
+               items: Vec<i32>,
+           }
+
+           let rc = Rc::new(Foo { items: vec![1, 2] });
+           let mut items = subrc!(rc.items);
+           assert_eq!(items.with_mut(|items| items.push(3)), Err(NotUnique));
+
+           drop(rc);
+           assert_eq!(items.with_mut(|items| items.pop()), Ok(Some(2)));
+       ```
+    */
+    pub fn with_mut<R, F>(&mut self, f: F) -> Result<R, NotUnique>
+    where
+        F: FnOnce(&mut U) -> R,
+    {
+        Self::get_mut(self).map(f).ok_or(NotUnique)
+    }
+
+    /**
+       Get a mutable reference to the target without checking that the owned value isn't shared,
+       like [`Rc::get_mut_unchecked`](alloc::rc::Rc::get_mut_unchecked).
+
+       Only available with the `nightly` feature.
+
+       # Safety
+       No other pointer to the owned value, whether an owner, a weak pointer or another [`Subptr`],
+       may be dereferenced for the duration of the returned borrow. Other projections may only be
+       used again once it ends, and targets which don't overlap this one aren't exempt.
+
+       # Panics
+       Panics if the target lives behind an indirection (see
+       [`new_indirect`](Subptr::new_indirect)), which the owner can't give mutable access to.
+
+       ## Example
+       ```rust
+           #![feature(get_mut_unchecked)]
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               value: i32,
+           }
+
+           let rc = Rc::new(Foo { value: 42 });
+           let mut subrc = subrc!(rc.value);
+           // SAFETY: `rc` isn't used while the mutable reference is alive.
+           unsafe { *Subrc::get_mut_unchecked(&mut subrc) += 1 };
+           assert_eq!(rc.value, 43);
+       ```
+    */
+    #[cfg(feature = "nightly")]
+    pub unsafe fn get_mut_unchecked(this: &mut Self) -> &mut U {
+        let offset = match get_offset(&*this.owner, this.get()) {
+            Ok(offset) => offset,
+            Err(e) => panic!("can't mutate a projection behind an indirection: {}", e),
+        };
+        let metadata = Pointee::metadata(this.get());
+        let t_ptr = P::get_mut_unchecked(&mut this.owner) as *mut P::Target as *mut u8;
+        // SAFETY: the target lies at `offset` within the owned value, see `get_mut`.
+        this.ptr = NonNull::new_unchecked(U::from_raw_parts(t_ptr.add(offset), metadata) as *mut U);
+        this.ptr.as_mut()
+    }
+
+    /**
+       Get a mutable reference to the target, cloning the owned value first if it is shared, like
+       [`Rc::make_mut`](alloc::rc::Rc::make_mut). The projection is then moved to the same subregion
+       of the clone, which this becomes the owner of.
+
+       This is an associated function, so it doesn't shadow a `make_mut` method of the target.
+
+       # Panics
+       Panics if the target lives behind an indirection (see
+       [`new_indirect`](Subptr::new_indirect)), which can't be found again in the clone.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           #[derive(Clone)]
+           struct Foo {
+               value: i32,
+           }
+
+           let rc = Rc::new(Foo { value: 42 });
+           let mut subrc = subrc!(rc.value);
+           *Subrc::make_mut(&mut subrc) += 1;
+           assert_eq!(*subrc, 43);
+           // the original value is left untouched
+           assert_eq!(rc.value, 42);
+       ```
+    */
+    pub fn make_mut(this: &mut Self) -> &mut U
+    where
+        P::Target: Clone,
+    {
+        let offset = match get_offset(&*this.owner, this.get()) {
+            Ok(offset) => offset,
+            Err(e) => panic!("can't move a projection behind an indirection: {}", e),
+        };
+        let metadata = Pointee::metadata(this.get());
+        let t_ptr = P::make_mut(&mut this.owner) as *mut P::Target as *mut u8;
+        // SAFETY: the target lies at `offset` within the owned value, or within its clone, which
+        // we have unique access to. See `get_mut`.
+        unsafe {
+            this.ptr =
+                NonNull::new_unchecked(U::from_raw_parts(t_ptr.add(offset), metadata) as *mut U);
+            this.ptr.as_mut()
+        }
+    }
+
+    /**
+       Return whether both projections share the same owned value and point to the same target
+       within it, like [`Rc::ptr_eq`](alloc::rc::Rc::ptr_eq).
+
+       Targets starting at the same address but with different metadata (e.g. slices of different
+       lengths) are not the same.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new([1, 1]);
+           let first = Subrc::new(rc.clone(), |t| &t[0]);
+           assert!(Subrc::ptr_eq(&first, &first.clone()));
+           assert!(!Subrc::ptr_eq(&first, &Subrc::new(rc.clone(), |t| &t[1])));
+           assert!(!Subrc::ptr_eq(&first, &Subrc::new(Rc::new([1, 1]), |t| &t[0])));
+       ```
+    */
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Self::same_owner(this, other) && ptr::eq(this.ptr.as_ptr(), other.ptr.as_ptr())
+    }
+
+    /**
+       Return whether both projections share the same owned value, whatever they point to within it.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new((1u8, 2u32));
+           let first = Subrc::new(rc.clone(), |t| &t.0);
+           assert!(Subrc::same_owner(&first, &Subrc::new(rc.clone(), |t| &t.1)));
+           assert!(!Subrc::same_owner(&first, &Subrc::new(Rc::new((1u8, 2u32)), |t| &t.0)));
+       ```
+    */
+    pub fn same_owner<V>(this: &Self, other: &Subptr<P, V>) -> bool
+    where
+        V: ?Sized + Pointee,
+    {
+        ptr::addr_eq(&*this.owner, &*other.owner)
+    }
+
+    /**
+       Get the owner of the owned value, e.g. to create another projection from it.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new((1u8, 2u32));
+           let first = Subrc::new(rc.clone(), |t| &t.0);
+           assert!(Rc::ptr_eq(first.owner(), &rc));
+
+           let second = Subrc::new(first.owner().clone(), |t| &t.1);
+           assert_eq!(*second, 2);
+       ```
+    */
+    pub fn owner(&self) -> &P {
+        &self.owner
+    }
+
+    /// Get the number of owners of the owned value, like
+    /// [`Rc::strong_count`](alloc::rc::Rc::strong_count).
+    pub fn strong_count(this: &Self) -> usize {
+        P::strong_count(&this.owner)
+    }
+
+    /// Get the number of weak pointers to the owned value, like
+    /// [`Rc::weak_count`](alloc::rc::Rc::weak_count).
+    pub fn weak_count(this: &Self) -> usize {
+        P::weak_count(&this.owner)
+    }
+
+    /**
+       Return whether this is the only owner of the owned value, and there are no weak pointers to
+       it either, i.e. whether [`get_mut`](Subptr::get_mut) would succeed for a target within it.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let subrc = Subrc::new(Rc::new((1u8, 2u32)), |t| &t.1);
+           assert!(Subrc::is_unique(&subrc));
+
+           let weak = Subrc::downgrade(&subrc);
+           assert!(!Subrc::is_unique(&subrc));
+           drop(weak);
+
+           let clone = subrc.clone();
+           assert!(!Subrc::is_unique(&subrc));
+       ```
+    */
+    pub fn is_unique(this: &Self) -> bool {
+        P::is_unique(&this.owner)
+    }
+
+    /**
+       Get a raw pointer to the target, without creating a reference to it, like
+       [`Rc::as_ptr`](alloc::rc::Rc::as_ptr).
+
+       The pointer is valid for as long as the owned value is alive.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new((1u8, 2u32));
+           let subrc = Subrc::new(rc.clone(), |t| &t.1);
+           assert_eq!(Subrc::as_ptr(&subrc), &rc.1 as *const u32);
+       ```
+    */
+    pub fn as_ptr(this: &Self) -> *const U {
+        this.ptr.as_ptr()
+    }
+
+    /**
+       Get the offset of the target within the owned value, in bytes, e.g. to record which
+       subregion a projection points to.
+
+       Returns [`None`] if the target lives behind an indirection, see
+       [`new_indirect`](Subptr::new_indirect).
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new((1u32, String::from("two")));
+           let first = Subrc::new(rc.clone(), |t| &t.0);
+           let offset = std::mem::offset_of!((u32, String), 0);
+           assert_eq!(Subrc::projection_offset(&first), Some(offset));
+
+           let second = Subrc::new_indirect(rc.clone(), |t| t.1.as_str());
+           assert_eq!(Subrc::projection_offset(&second), None);
+       ```
+    */
+    pub fn projection_offset(this: &Self) -> Option<usize> {
+        get_offset(&*this.owner, this.get()).ok()
+    }
+
+    /**
+       Get the address of the owned value, which is shared by all the projections into it.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new((1u32, 2u8));
+           let subrc = Subrc::new(rc.clone(), |t| &t.1);
+           assert_eq!(Subrc::owner_addr(&subrc), Rc::as_ptr(&rc).addr());
+       ```
+    */
+    pub fn owner_addr(this: &Self) -> usize {
+        (&*this.owner as *const P::Target).addr()
+    }
+
+    /**
+       Clone the target into an owned value.
+
+       This is an associated function, so it doesn't shadow a `cloned` method of the target, such
+       as [`Option::cloned`].
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let subrc = Subrc::new(Rc::new((1u8, vec![2, 3])), |t| &t.1);
+           let items: Vec<i32> = Subrc::cloned(&subrc);
+           assert_eq!(items, [2, 3]);
+       ```
+    */
+    pub fn cloned(this: &Self) -> U
+    where
+        U: Clone,
+    {
+        this.get().clone()
+    }
+
+    pub fn get(&self) -> &U {
+        #[cfg(feature = "debug-validate")]
+        self.debug_validate();
+        // SAFETY: the target is kept alive by the owner, see `from_raw_parts`.
+        unsafe { self.ptr.as_ref() }
+    }
+
+    // Check that the projection still upholds the invariants it was created with, to catch
+    // handles corrupted by unsafe code (e.g. reassembled from mismatched raw parts) early.
+    #[cfg(feature = "debug-validate")]
+    #[track_caller]
+    fn debug_validate(&self) {
+        debug_assert!(
+            P::strong_count(&self.owner) > 0,
+            "the owner was deallocated"
+        );
+        // SAFETY: only the address and metadata of the target are looked at.
+        let target = unsafe { self.ptr.as_ref() };
+        debug_assert!(
+            self.ptr
+                .addr()
+                .get()
+                .is_multiple_of(mem::align_of_val(target)),
+            "the target is misaligned"
+        );
+        if let Some(offset) = self.offset {
+            debug_assert_eq!(
+                get_offset(&*self.owner, target),
+                Ok(offset),
+                "the target moved within the owned value"
+            );
+        }
+    }
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner,
+{
+    /**
+       Implementation detail of the [`subrc_field`](crate::subrc_field) macro.
+
+       # Safety
+       `offset` must be the offset of the field `_getter` projects to, within the value owned by
+       `owner`. `_getter` is never called, it only pins down the owner and field types.
+
+       The macro computes `offset` with `offset_of!` for the very field the getter projects to,
+       which can't go through a deref since `offset_of!` would reject it.
+    */
+    #[doc(hidden)]
+    pub unsafe fn from_field_offset<F>(owner: P, offset: usize, _getter: F) -> Self
+    where
+        F: FnOnce(&P::Target) -> &U,
+    {
+        let t_ptr = &*owner as *const P::Target as *const u8;
+        Self::from_raw_parts(owner, t_ptr.add(offset) as *const U)
+    }
+}
+
+impl<P, E> Subptr<P, [E]>
+where
+    P: SharedOwner,
+{
+    /**
+       Create a [`Subptr`] pointer to a sub-range of a slice within the value owned by `owner`.
+       The `getter` function specifies the slice, like in [`new`](Subptr::new).
+
+       # Panics
+       Panics if `range` is out of the bounds of the slice, or if `getter` returns anything other
+       than a reference to a subregion of the owned value.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           struct Packet {
+               data: [u8; 16],
+           }
+
+           let rc = Rc::new(Packet { data: [7; 16] });
+           let payload = Subrc::subslice(rc.clone(), |packet| &packet.data, 2..10);
+           assert_eq!(payload.len(), 8);
+           assert!(std::ptr::eq(&*payload, &rc.data[2..10]));
+       ```
+    */
+    #[track_caller]
+    pub fn subslice<F, R>(owner: P, getter: F, range: R) -> Self
+    where
+        F: FnOnce(&P::Target) -> &[E],
+        R: RangeBounds<usize>,
+    {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        Self::new(owner, |t| &getter(t)[bounds])
+    }
+}
+
+impl<P, E> Subptr<P, E>
+where
+    P: SharedOwner,
+{
+    /**
+       Create a [`Subptr`] to the element at `index` of the slice returned by `getter`, or [`None`]
+       if it is out of bounds.
+
+       The slice may lie within the owned value, or behind an indirection like the content of a
+       [`Vec`] or a boxed slice field, see [`new_indirect`](Subptr::new_indirect).
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           struct Playlist {
+               songs: Vec<String>,
+           }
+
+           let rc = Rc::new(Playlist { songs: vec![String::from("intro"), String::from("outro")] });
+           let outro = Subrc::project_index(&rc, |playlist| &playlist.songs, 1).unwrap();
+           assert!(std::ptr::eq(&*outro, &rc.songs[1]));
+           assert!(Subrc::project_index(&rc, |playlist| &playlist.songs, 2).is_none());
+       ```
+    */
+    pub fn project_index<F>(owner: &P, getter: F, index: usize) -> Option<Self>
+    where
+        F: FnOnce(&P::Target) -> &[E],
+    {
+        let ptr = getter(owner).get(index)? as *const E;
+        // SAFETY: the element was borrowed from the owned value, which the clone of `owner` keeps
+        // alive and shared, see `new_indirect`.
+        Some(unsafe { Self::from_raw_parts(owner.clone(), ptr) })
+    }
+}
+
+impl<P, U> Subptr<P, Option<U>>
+where
+    P: SharedOwner,
+{
+    /**
+       Turn a projection to an [`Option`] into an optional projection to its content, keeping the
+       same owner alive.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               bar: Option<i32>,
+               baz: Option<i32>,
+           }
+
+           let rc = Rc::new(Foo { bar: Some(42), baz: None });
+           let bar: Subrc<Foo, i32> = subrc!(rc.bar).transpose().unwrap();
+           assert_eq!(*bar, 42);
+           assert!(subrc!(rc.baz).transpose().is_none());
+       ```
+    */
+    pub fn transpose(self) -> Option<Subptr<P, U>> {
+        self.try_map(Option::as_ref)
+    }
+}
+
+impl<P, U, E> Subptr<P, Result<U, E>>
+where
+    P: SharedOwner,
+{
+    /**
+       Turn a projection to a [`Result`] into a projection to either its [`Ok`] or its [`Err`]
+       content, keeping the same owner alive.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               bar: Result<i32, String>,
+           }
+
+           let rc = Rc::new(Foo { bar: Err(String::from("oops")) });
+           let err: Subrc<Foo, String> = subrc!(rc.bar).transpose().err().unwrap();
+           assert_eq!(*err, "oops");
+       ```
+    */
+    pub fn transpose(self) -> Result<Subptr<P, U>, Subptr<P, E>> {
+        if self.is_ok() {
+            Ok(self.map(|result| match result {
+                Ok(u) => u,
+                Err(_) => unreachable!("checked to be `Ok` above"),
+            }))
+        } else {
+            Err(self.map(|result| match result {
+                Ok(_) => unreachable!("checked to be `Err` above"),
+                Err(e) => e,
+            }))
+        }
+    }
+}
+
+impl<P, Q> Subptr<P, Q>
+where
+    P: SharedOwner,
+    Q: SharedOwner,
+{
+    /**
+       Turn a projection to a shared owner field, e.g. an `Rc<U>`, into a clone of that field,
+       releasing the outer owner.
+
+       The returned handle points to `U` directly, instead of through the outer owner.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::{subrc, Subrc};
+           struct Foo {
+               bar: Rc<String>,
+           }
+
+           let rc = Rc::new(Foo { bar: Rc::new(String::from("bar")) });
+           let bar: Rc<String> = Subrc::adopt_inner(subrc!(rc.bar));
+           assert!(Rc::ptr_eq(&bar, &rc.bar));
+           assert_eq!(Rc::strong_count(&rc), 1);
+       ```
+    */
+    pub fn adopt_inner(this: Self) -> Q {
+        this.get().clone()
+    }
+}
+
+impl<T: ?Sized, U: Clone> Subptr<Rc<T>, U> {
+    /**
+       Clone the target into a fresh [`Rc`], which doesn't keep the owned value alive, so that a
+       possibly large owner can be dropped.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new((1u8, String::from("two")));
+           let two: Rc<String> = Subrc::to_rc(&Subrc::new(rc.clone(), |t| &t.1));
+           assert_eq!(*two, "two");
+           assert_eq!(Rc::strong_count(&rc), 1);
+       ```
+    */
+    pub fn to_rc(this: &Self) -> Rc<U> {
+        Rc::new(this.get().clone())
+    }
+}
+
+impl<T: ?Sized, U: Clone> Subptr<Arc<T>, U> {
+    /// Clone the target into a fresh [`Arc`], like [`to_rc`](Subptr::to_rc) does for a
+    /// [`Subrc`](crate::Subrc).
+    pub fn to_arc(this: &Self) -> Arc<U> {
+        Arc::new(this.get().clone())
+    }
+}
+
+impl<P> Subptr<P, P::Target>
+where
+    P: SharedOwner,
+    P::Target: Pointee,
+{
+    /**
+       Create a [`Subptr`] pointing to the whole value owned by `owner`.
+
+       This is the identity projection, which never fails, even for zero-sized values.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           let rc = Rc::new(());
+           let whole = Subrc::whole(rc.clone());
+           assert!(std::ptr::eq(&*whole, &*rc));
+
+           // or, equivalently
+           let whole: Subrc<(), ()> = rc.into();
+       ```
+    */
+    pub fn whole(owner: P) -> Self {
+        let ptr = &*owner as *const P::Target;
+        // SAFETY: the owned value trivially lies within itself.
+        unsafe { Self::from_raw_parts(owner, ptr) }
+    }
+}
+
+impl<P> From<P> for Subptr<P, P::Target>
+where
+    P: SharedOwner,
+    P::Target: Pointee,
+{
+    fn from(owner: P) -> Self {
+        Self::whole(owner)
+    }
+}
+
+impl<P, U> Deref for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<P, U> AsRef<U> for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    fn as_ref(&self) -> &U {
+        self.get()
+    }
+}
+
+/**
+ Since a [`Subptr`] compares and hashes like its target, it can be looked up by the target in a map
+ or set keyed by projections.
+
+ # Example
+```rust
+# use std::{collections::HashMap, rc::Rc};
+# use subrc::Subrc;
+let rc = Rc::new((String::from("one"), String::from("two")));
+let mut map = HashMap::new();
+map.insert(Subrc::new_indirect(rc.clone(), |t| t.0.as_str()), 1);
+map.insert(Subrc::new_indirect(rc.clone(), |t| t.1.as_str()), 2);
+assert_eq!(map.get("two"), Some(&2));
+```
+*/
+impl<P, U> Borrow<U> for Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee,
+{
+    fn borrow(&self) -> &U {
+        self.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{any::Any, collections::BTreeSet, rc::Rc, sync::Arc};
+
+    use super::{check_offset, get_offset};
+    use crate::{NotUnique, ProjectionError, Subarc, Subrc};
+
+    struct Foo {
+        _value: i32,
+        items: [u16; 4],
+        name: [u8; 5],
+    }
+
+    fn foo() -> Foo {
+        Foo {
+            _value: 42,
+            items: [1, 2, 3, 4],
+            name: *b"hello",
+        }
+    }
+
+    #[test]
+    fn test_subrc_slice() {
+        let rc = Rc::new(foo());
+        let subrc: Subrc<Foo, [u16]> = Subrc::new(rc.clone(), |foo| &foo.items[1..]);
+        assert_eq!(&*subrc, &[2, 3, 4]);
+        assert!(std::ptr::eq(&*subrc, &rc.items[1..]));
+    }
+
+    #[test]
+    fn test_subrc_dyn_any() {
+        let rc = Rc::new(foo());
+        let subrc = Subrc::<_, dyn Any>::new(rc.clone(), |foo| &foo.items);
+        assert_eq!(subrc.downcast_ref::<[u16; 4]>(), Some(&[1, 2, 3, 4]));
+        assert!(std::ptr::addr_eq(&*subrc, &rc.items));
+    }
+
+    #[test]
+    fn test_subarc_dyn_any_is_send() {
+        let arc = Arc::new(foo());
+        let subarc = Subarc::<_, dyn Any + Send + Sync>::new(arc, |foo| &foo.name);
+
+        let handle = std::thread::spawn(move || subarc.is::<[u8; 5]>());
+        assert!(handle.join().unwrap());
+    }
+
+    #[test]
+    fn test_subrc_unsized_owner_slice() {
+        let rc: Rc<[u32]> = Rc::from([1, 2, 3]);
+        let subrc = Subrc::new(rc.clone(), |items| &items[2]);
+        assert_eq!(*subrc, 3);
+        assert!(std::ptr::eq(&*subrc, &rc[2]));
+    }
+
+    #[test]
+    fn test_subrc_unsized_owner_str() {
+        let rc: Rc<str> = Rc::from("hello world");
+        let subrc = Subrc::new(rc.clone(), |s| &s[6..]);
+        assert_eq!(&*subrc, "world");
+    }
+
+    #[test]
+    fn test_subrc_unsized_owner_dyn() {
+        let rc: Rc<dyn Any> = Rc::new(42u64);
+        let subrc = Subrc::new(rc.clone(), |any| any.downcast_ref::<u64>().unwrap());
+        assert_eq!(*subrc, 42);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_for_outside_unsized_owner() {
+        let rc: Rc<[u32]> = Rc::from([1, 2, 3]);
+        static OTHER: [u32; 1] = [4];
+        let _subrc = Subrc::new(rc, |_| &OTHER[0]);
+    }
+
+    #[test]
+    fn test_try_new() {
+        static OTHER: u16 = 0;
+        let rc = Rc::new(foo());
+        let item = Subrc::try_new(rc.clone(), |foo| &foo.items[1]).unwrap();
+        assert!(std::ptr::eq(&*item, &rc.items[1]));
+        assert!(Subrc::try_new(rc, |_| &OTHER).is_err());
+    }
+
+    #[test]
+    fn test_get_offset_errors() {
+        #[repr(C)]
+        struct Pair {
+            a: u32,
+            b: u32,
+        }
+
+        let pair = Pair { a: 1, b: 2 };
+        assert_eq!(get_offset(&pair, &pair.b), Ok(4));
+        assert_eq!(
+            get_offset(&pair.b, &pair.a),
+            Err(ProjectionError::BeforeOwner)
+        );
+        assert_eq!(
+            get_offset(&pair.a, &pair.b),
+            Err(ProjectionError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_check_offset() {
+        assert_eq!(check_offset((16, 8), (20, 4), 4), Ok(4));
+        assert_eq!(check_offset((16, 8), (16, 8), 8), Ok(0));
+        assert_eq!(
+            check_offset((16, 8), (12, 4), 4),
+            Err(ProjectionError::BeforeOwner)
+        );
+        // starting within the owned value, but extending past its end
+        assert_eq!(
+            check_offset((16, 8), (20, 8), 4),
+            Err(ProjectionError::OutOfBounds)
+        );
+        assert_eq!(
+            check_offset((16, 8), (24, 4), 4),
+            Err(ProjectionError::OutOfBounds)
+        );
+        assert_eq!(
+            check_offset((16, 8), (18, 2), 4),
+            Err(ProjectionError::Misaligned)
+        );
+        // zero-sized regions may lie at the end
+        assert_eq!(check_offset((16, 8), (24, 0), 4), Ok(8));
+        assert_eq!(check_offset((16, 0), (16, 0), 1), Ok(0));
+        assert_eq!(
+            check_offset((16, 8), (25, 0), 1),
+            Err(ProjectionError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_reference_past_the_end() {
+        #[repr(C)]
+        struct Outer {
+            pair: [u32; 2],
+            c: u32,
+        }
+
+        let outer = Outer { pair: [1, 2], c: 3 };
+        assert_eq!(outer.c, 3);
+        // SAFETY: the two `u32`s from `pair[1]` on are `pair[1]` and `c`.
+        let tail = unsafe {
+            &*(&outer as *const Outer)
+                .cast::<u32>()
+                .add(1)
+                .cast::<[u32; 2]>()
+        };
+        assert_eq!(
+            get_offset(&outer.pair, tail),
+            Err(ProjectionError::OutOfBounds)
+        );
+        assert_eq!(get_offset(&outer, tail), Ok(4));
+    }
+
+    #[test]
+    fn test_try_new_opt() {
+        let rc = Rc::new(Some(foo()));
+        let items = Subrc::try_new_opt(rc.clone(), |foo| foo.as_ref().map(|foo| &foo.items));
+        assert!(std::ptr::eq(
+            &*items.unwrap(),
+            &(*rc).as_ref().unwrap().items
+        ));
+        assert!(Subrc::<_, u8>::try_new_opt(rc, |_| None).is_none());
+    }
+
+    #[test]
+    fn test_try_new_with() {
+        let rc = Rc::new(foo());
+        let item = Subrc::try_new_with(rc.clone(), |foo| foo.items.get(1).ok_or("missing"));
+        assert!(std::ptr::eq(&*item.unwrap(), &rc.items[1]));
+        let item = Subrc::try_new_with(rc, |foo| foo.items.get(100).ok_or("missing"));
+        assert_eq!(item.err(), Some("missing"));
+    }
+
+    #[test]
+    fn test_new_unchecked() {
+        let rc = Rc::new(foo());
+        let item = unsafe { Subrc::new_unchecked(rc.clone(), |foo| &foo.items[3]) };
+        assert!(std::ptr::eq(&*item, &rc.items[3]));
+    }
+
+    #[test]
+    fn test_raw_parts_roundtrip() {
+        let rc = Rc::new(foo());
+        let subrc: Subrc<Foo, [u16]> = Subrc::new(rc.clone(), |foo| &foo.items[1..3]);
+        let (owner, ptr) = Subrc::into_raw_parts(subrc);
+        assert_eq!(ptr.len(), 2);
+
+        let subrc: Subrc<Foo, [u16]> = unsafe { Subrc::from_raw_parts(owner, ptr) };
+        assert!(std::ptr::eq(&*subrc, &rc.items[1..3]));
+    }
+
+    #[test]
+    fn test_whole() {
+        let rc = Rc::new(foo());
+        let whole = Subrc::whole(rc.clone());
+        assert!(std::ptr::eq(&*whole, &*rc));
+
+        let items = whole.map(|foo| &foo.items);
+        assert!(std::ptr::eq(&*items, &rc.items));
+    }
+
+    #[test]
+    fn test_whole_zst_and_unsized() {
+        let arc = Arc::new(());
+        let whole: Subarc<(), ()> = arc.clone().into();
+        assert!(std::ptr::eq(&*whole, &*arc));
+
+        let rc: Rc<str> = Rc::from("hello");
+        let whole = Subrc::from(rc);
+        assert_eq!(&*whole, "hello");
+    }
+
+    #[test]
+    fn test_zst_projections() {
+        use std::marker::PhantomData;
+
+        struct Tagged {
+            value: u32,
+            tag: PhantomData<u8>,
+        }
+
+        struct Marker {
+            unit: (),
+            empty: [u64; 0],
+        }
+
+        let rc = Rc::new(Tagged {
+            value: 1,
+            tag: PhantomData,
+        });
+        let tag = Subrc::new(rc.clone(), |t| &t.tag);
+        assert!(std::ptr::eq(&*tag, &rc.tag));
+        let end = Subrc::new(rc.clone(), |t| &std::slice::from_ref(&t.value)[1..]);
+        assert!(end.is_empty());
+        assert_eq!(rc.value, 1);
+
+        let rc = Rc::new(Marker {
+            unit: (),
+            empty: [],
+        });
+        let unit = Subrc::new(rc.clone(), |m| &m.unit);
+        assert!(std::ptr::eq(&*unit, &rc.unit));
+        let mut empty = Subrc::new(rc.clone(), |m| &m.empty);
+        assert_eq!(Subrc::projection_offset(&empty), Some(0));
+        drop((rc, unit));
+        assert_eq!(Subrc::get_mut(&mut empty), Some(&mut []));
+    }
+
+    #[test]
+    fn test_subslice() {
+        let rc = Rc::new(foo());
+        let subslice = Subrc::subslice(rc.clone(), |foo| &foo.items, 1..=2);
+        assert!(std::ptr::eq(&*subslice, &rc.items[1..3]));
+
+        let tail = Subrc::subslice(rc.clone(), |foo| &foo.items[1..], 1..);
+        assert!(std::ptr::eq(&*tail, &rc.items[2..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_for_subslice_out_of_range() {
+        let rc = Rc::new(foo());
+        let _subslice = Subrc::subslice(rc, |foo| &foo.items, 2..5);
+    }
+
+    #[test]
+    fn test_transpose_option() {
+        let rc = Rc::new((Some(foo()), None::<u8>));
+        let some = Subrc::new(rc.clone(), |t| &t.0).transpose().unwrap();
+        assert!(std::ptr::eq(&*some, rc.0.as_ref().unwrap()));
+        assert!(Subrc::new(rc, |t| &t.1).transpose().is_none());
+    }
+
+    #[test]
+    fn test_transpose_result() {
+        let rc = Rc::new((Ok::<_, u32>(foo()), Err::<u8, _>(42u32)));
+        let ok = Subrc::new(rc.clone(), |t| &t.0).transpose().ok().unwrap();
+        assert!(std::ptr::eq(&*ok, rc.0.as_ref().unwrap()));
+
+        let err = Subrc::new(rc.clone(), |t| &t.1).transpose().err().unwrap();
+        assert!(std::ptr::eq(&*err, rc.1.as_ref().unwrap_err()));
+    }
+
+    #[test]
+    fn test_map() {
+        let rc = Rc::new(foo());
+        let items = Subrc::new(rc.clone(), |foo| &foo.items);
+        let item = items.clone().map(|items| &items[2]);
+        assert_eq!(*item, 3);
+        assert!(std::ptr::eq(&*item, &rc.items[2]));
+        assert_eq!(Rc::strong_count(&rc), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_for_unrelated_map() {
+        static OTHER: u16 = 0;
+        let rc = Rc::new(foo());
+        let _item = Subrc::new(rc, |foo| &foo.items).map(|_| &OTHER);
+    }
+
+    #[test]
+    fn test_project_index() {
+        let rc = Rc::new((
+            vec![1u8, 2, 3],
+            Box::new([4u16, 5]) as Box<[u16]>,
+            [6u32; 2],
+        ));
+        let second = Subrc::project_index(&rc, |t| &t.0, 1).unwrap();
+        assert!(std::ptr::eq(&*second, &rc.0[1]));
+        let first = Subrc::project_index(&rc, |t| &t.1, 0).unwrap();
+        assert!(std::ptr::eq(&*first, &rc.1[0]));
+        let last = Subrc::project_index(&rc, |t| &t.2, 1).unwrap();
+        assert!(std::ptr::eq(&*last, &rc.2[1]));
+        assert!(Subrc::project_index(&rc, |t| &t.1, 2).is_none());
+        assert_eq!(Rc::strong_count(&rc), 4);
+    }
+
+    #[test]
+    fn test_map_split() {
+        let rc = Rc::new((1u8, [2u16, 3, 4]));
+        let (first, rest) = Subrc::new(rc.clone(), |t| &t.1).map_split(|a| a.split_at(1));
+        assert!(std::ptr::eq(&*first, &rc.1[..1]));
+        assert!(std::ptr::eq(&*rest, &rc.1[1..]));
+        assert_eq!(Rc::strong_count(&rc), 3);
+
+        let boxed = Rc::new(Box::new((5u8, 6u32)));
+        let (left, right) =
+            Subrc::new_indirect(boxed.clone(), |b| &**b).map_split(|t| (&t.0, &t.1));
+        assert_eq!((*left, *right), (5, 6));
+    }
+
+    #[test]
+    fn test_invalid_projection_message() {
+        let rc = Rc::new((1u8, 2u16));
+        let owner = Rc::as_ptr(&rc).addr();
+        static OTHER: u32 = 3;
+        let error = std::panic::catch_unwind(|| Subrc::new(rc.clone(), |_| &OTHER)).unwrap_err();
+        let message = error.downcast::<String>().unwrap();
+        assert!(message.starts_with("getter did not return portion of the object: "));
+        assert!(message.contains("a `u32` at "));
+        assert!(message.contains(&format!(
+            "from the owned `(u8, u16)` at {:#x}..{:#x}",
+            owner,
+            owner + 4
+        )));
+        let offset = (&OTHER as *const u32).addr().wrapping_sub(owner) as isize;
+        assert!(message.contains(&format!("at offset {} ", offset)));
+    }
+
+    #[test]
+    #[should_panic(expected = "getter did not return portion of the object")]
+    fn test_map_split_invalid() {
+        static OTHER: u16 = 3;
+        let rc = Rc::new((1u8, 2u16));
+        let _ = Subrc::new(rc, |t| &t.1).map_split(|u| (u, &OTHER));
+    }
+
+    #[test]
+    fn test_try_map() {
+        let rc = Rc::new(foo());
+        let items = Subrc::new(rc.clone(), |foo| &foo.items);
+        assert!(items.clone().try_map(|items| items.get(4)).is_none());
+
+        let item = items.try_map(|items| items.get(3)).unwrap();
+        assert!(std::ptr::eq(&*item, &rc.items[3]));
+    }
+
+    #[test]
+    fn test_try_map_with() {
+        let rc = Rc::new(foo());
+        let name = Subrc::new(rc.clone(), |foo| &foo.name);
+        assert!(name
+            .clone()
+            .try_map_with(|name| std::str::from_utf8(&name[..]))
+            .is_ok());
+        assert!(name
+            .try_map_with(|_| Err::<&str, _>("no name"))
+            .is_err_and(|e| e == "no name"));
+    }
+
+    #[test]
+    fn test_from_ref() {
+        static OTHER: u16 = 0;
+        let rc = Rc::new(foo());
+        let max = rc.items.iter().max().unwrap();
+        let item = unsafe { Subrc::from_ref(rc.clone(), max) }.unwrap();
+        assert!(std::ptr::eq(&*item, &rc.items[3]));
+        assert!(unsafe { Subrc::from_ref(rc, &OTHER) }.is_err());
+    }
+
+    #[test]
+    fn test_new_indirect() {
+        let rc = Rc::new((Box::new(foo()), vec![1u8, 2, 3], String::from("hello")));
+        let boxed = Subrc::new_indirect(rc.clone(), |t| &*t.0);
+        assert!(std::ptr::eq(&*boxed, &*rc.0));
+        let element = Subrc::new_indirect(rc.clone(), |t| &t.1[2]);
+        assert_eq!(*element, 3);
+        let s: Subrc<_, str> = Subrc::new_indirect(rc.clone(), |t| &t.2[1..]);
+        assert_eq!(&*s, "ello");
+
+        // projecting further down stays within the indirect target
+        let items = boxed.map(|foo| &foo.items[1..]);
+        assert!(std::ptr::eq(&*items, &rc.0.items[1..]));
+        assert_eq!(Rc::strong_count(&rc), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_for_indirect_new() {
+        let rc = Rc::new(Box::new(foo()));
+        let _items = Subrc::new(rc, |foo| &foo.items);
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let rc = Rc::new(foo());
+        let weak = Rc::downgrade(&rc);
+        let mut items = Subrc::new(rc, |foo| &foo.items);
+        let clone = items.clone();
+        assert!(Subrc::get_mut(&mut items).is_none());
+
+        drop(clone);
+        assert!(Subrc::get_mut(&mut items).is_none());
+
+        drop(weak);
+        Subrc::get_mut(&mut items).unwrap()[1] = 42;
+        assert_eq!(*items, [1, 42, 3, 4]);
+        let item = items.map(|items| &items[1]);
+        assert_eq!(*item, 42);
+    }
+
+    #[test]
+    fn test_get_mut_indirect() {
+        let mut boxed = Subrc::new_indirect(Rc::new(Box::new(foo())), |foo| &**foo);
+        assert!(Subrc::get_mut(&mut boxed).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn test_coerce_unsized() {
+        let rc = Rc::new(foo());
+        let items: Subrc<Foo, [u16; 4]> = Subrc::new(rc.clone(), |foo| &foo.items);
+        let items: Subrc<Foo, [u16]> = items;
+        assert_eq!(&*items, &[1, 2, 3, 4]);
+
+        let name: Subrc<Foo, [u8; 5]> = Subrc::new(rc.clone(), |foo| &foo.name);
+        let weak = Subrc::downgrade(&name);
+        let weak: crate::WeakSubptr<Rc<Foo>, dyn Any> = weak;
+        let name: Subrc<Foo, dyn Any> = name;
+        assert_eq!(name.downcast_ref::<[u8; 5]>(), Some(b"hello"));
+        assert!(weak.upgrade().unwrap().is::<[u8; 5]>());
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn test_get_mut_unchecked() {
+        let rc = Rc::new(foo());
+        let mut items = Subrc::new(rc.clone(), |foo| &foo.items);
+        unsafe { Subrc::get_mut_unchecked(&mut items)[2] = 42 };
+        assert_eq!(rc.items, [1, 2, 42, 4]);
+        assert_eq!(*items, [1, 2, 42, 4]);
+    }
+
+    #[test]
+    fn test_with_mut() {
+        let rc = Rc::new(foo());
+        let mut name = Subrc::new(rc.clone(), |foo| &foo.name);
+        assert_eq!(name.with_mut(|name| name[0] = b'j'), Err(NotUnique));
+
+        drop(rc);
+        assert_eq!(name.with_mut(|name| name[0] = b'j'), Ok(()));
+        assert_eq!(&*name, b"jello");
+    }
+
+    #[test]
+    fn test_make_mut() {
+        #[derive(Clone)]
+        struct Bar {
+            _items: Vec<u32>,
+            name: [u8; 3],
+        }
+
+        let arc = Arc::new(Bar {
+            _items: vec![1, 2],
+            name: *b"bar",
+        });
+        let mut name: Subarc<Bar, [u8]> = Subarc::new(arc.clone(), |bar| &bar.name[1..]);
+        Subarc::make_mut(&mut name)[0] = b'o';
+        assert_eq!(&*name, b"or");
+        assert_eq!(&arc.name, b"bar");
+        assert_eq!(Arc::strong_count(&arc), 1);
+
+        // the clone is now uniquely owned, so it is mutated in place
+        let ptr = name.get() as *const [u8];
+        Subarc::make_mut(&mut name)[1] = b'o';
+        assert!(std::ptr::eq(ptr, &*name));
+        assert_eq!(&*name, b"oo");
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_for_make_mut_indirect() {
+        #[derive(Clone)]
+        struct Bar(Vec<u32>);
+
+        let mut item = Subrc::new_indirect(Rc::new(Bar(vec![1])), |bar| &bar.0[0]);
+        *Subrc::make_mut(&mut item) += 1;
+    }
+
+    #[test]
+    fn test_adopt_inner() {
+        let rc = Rc::new((1u8, Arc::new(foo())));
+        let inner = Subrc::adopt_inner(Subrc::new(rc.clone(), |t| &t.1));
+        assert!(Arc::ptr_eq(&inner, &rc.1));
+        assert_eq!(Rc::strong_count(&rc), 1);
+        assert_eq!(Arc::strong_count(&inner), 2);
+    }
+
+    #[test]
+    fn test_as_ref_and_borrow() {
+        fn len(s: impl AsRef<str>) -> usize {
+            s.as_ref().len()
+        }
+
+        let rc = Rc::new(foo());
+        let ell = || -> Subrc<Foo, str> {
+            Subrc::new(rc.clone(), |foo| {
+                std::str::from_utf8(&foo.name[1..4]).unwrap()
+            })
+        };
+        assert_eq!(len(ell()), 3);
+
+        let set: BTreeSet<_> = [ell()].into_iter().collect();
+        assert!(set.contains("ell"));
+        assert!(!set.contains("hello"));
+    }
+
+    #[test]
+    fn test_clone_without_clone_target() {
+        struct NotClone(u8);
+
+        let rc = Rc::new((1u8, NotClone(2)));
+        let subrc = Subrc::new(rc.clone(), |t| &t.1);
+        let clone = subrc.clone();
+        assert!(std::ptr::eq(&*clone, &*subrc));
+        assert_eq!(clone.0, 2);
+        assert_eq!(Rc::strong_count(&rc), 3);
+
+        let slice: Subrc<Foo, [u16]> = Subrc::new(Rc::new(foo()), |foo| &foo.items[..]);
+        assert_eq!(&*slice.clone(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_niche() {
+        use std::mem::size_of;
+
+        use crate::{Subarc, WeakSubptr};
+
+        assert_eq!(
+            size_of::<Option<Subrc<Foo, u8>>>(),
+            size_of::<Subrc<Foo, u8>>()
+        );
+        assert_eq!(
+            size_of::<Option<Subarc<Foo, [u8]>>>(),
+            size_of::<Subarc<Foo, [u8]>>()
+        );
+        assert_eq!(
+            size_of::<Option<WeakSubptr<Rc<Foo>, str>>>(),
+            size_of::<WeakSubptr<Rc<Foo>, str>>()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "debug-validate", debug_assertions))]
+    #[should_panic(expected = "the target moved within the owned value")]
+    fn test_debug_validate() {
+        let rc = Rc::new(foo());
+        let mut item = Subrc::new(rc.clone(), |foo| &foo.items[1]);
+        assert_eq!(*item, 2);
+        item.ptr = std::ptr::NonNull::from(&rc.items[2]);
+        let _ = *item;
+    }
+
+    #[test]
+    fn test_variance() {
+        fn shorten_owner<'a>(subrc: Subrc<&'static str, u8>) -> Subrc<&'a str, u8> {
+            subrc
+        }
+
+        let owner = Subrc::new_indirect(Rc::new("static"), |t| &t.as_bytes()[0]);
+        assert_eq!(*shorten_owner(owner), b's');
+    }
+
+    #[test]
+    fn test_dropck() {
+        // the owner may hold references which dangle while it is dropped, like an `Rc` may
+        let (subrc, value);
+        value = String::from("value");
+        subrc = Subrc::new(Rc::new((&value, 1u8)), |t| &t.1);
+        assert_eq!(*subrc, 1);
+    }
+
+    #[test]
+    fn test_ptr_eq_and_same_owner() {
+        let rc = Rc::new(foo());
+        let items: Subrc<Foo, [u16]> = Subrc::new(rc.clone(), |foo| &foo.items[..]);
+        let head: Subrc<Foo, [u16]> = Subrc::new(rc.clone(), |foo| &foo.items[..1]);
+        assert!(Subrc::ptr_eq(&items, &items.clone()));
+        assert!(!Subrc::ptr_eq(&items, &head));
+        assert!(Subrc::same_owner(&items, &head));
+
+        let name = Subrc::new(rc.clone(), |foo| &foo.name);
+        assert!(Subrc::same_owner(&items, &name));
+        let other = Subrc::new(Rc::new(foo()), |foo| &foo.name);
+        assert!(!Subrc::same_owner(&name, &other));
+        assert!(!Subrc::ptr_eq(&name, &other));
+    }
+
+    #[test]
+    fn test_owner() {
+        let rc = Rc::new(foo());
+        let name = Subrc::new(rc.clone(), |foo| &foo.name);
+        assert!(Rc::ptr_eq(name.owner(), &rc));
+        assert_eq!(Rc::strong_count(name.owner()), 2);
+
+        let arc = Arc::new(foo());
+        let items = Subarc::new(arc.clone(), |foo| &foo.items);
+        assert!(Arc::ptr_eq(items.owner(), &arc));
+    }
+
+    #[test]
+    fn test_into_owner() {
+        let rc = Rc::new(foo());
+        let name = Subrc::new(rc.clone(), |foo| &foo.name);
+        let owner = Subrc::into_owner(name);
+        assert!(Rc::ptr_eq(&owner, &rc));
+        assert_eq!(Rc::strong_count(&rc), 2);
+        drop(owner);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn test_counts() {
+        let rc = Rc::new(foo());
+        let name = Subrc::new(rc.clone(), |foo| &foo.name);
+        assert_eq!(Subrc::strong_count(&name), 2);
+        assert_eq!(Subrc::weak_count(&name), 0);
+        assert!(!Subrc::is_unique(&name));
+
+        drop(rc);
+        assert!(Subrc::is_unique(&name));
+        let weak = Subrc::downgrade(&name);
+        assert_eq!(Subrc::weak_count(&name), 1);
+        assert!(!Subrc::is_unique(&name));
+        drop(weak);
+
+        let arc = Subarc::new(Arc::new(foo()), |foo| &foo.items);
+        let clone = arc.clone();
+        assert_eq!(Subarc::strong_count(&arc), 2);
+        drop(clone);
+        assert!(Subarc::is_unique(&arc));
+    }
+
+    #[test]
+    fn test_as_ptr() {
+        let rc = Rc::new(foo());
+        let items: Subrc<Foo, [u16]> = Subrc::new(rc.clone(), |foo| &foo.items[1..]);
+        let ptr = Subrc::as_ptr(&items);
+        assert!(std::ptr::eq(ptr, &rc.items[1..]));
+        assert_eq!(unsafe { &*ptr }, &[2, 3, 4]);
+    }
+
+    #[test]
+    fn test_projection_offset_and_owner_addr() {
+        let rc = Rc::new(foo());
+        let items = Subrc::new(rc.clone(), |foo| &foo.items[1]);
+        let offset = std::mem::offset_of!(Foo, items) + 2;
+        assert_eq!(Subrc::projection_offset(&items), Some(offset));
+        assert_eq!(Subrc::owner_addr(&items), Rc::as_ptr(&rc).addr());
+        assert_eq!(
+            Subrc::owner_addr(&items) + offset,
+            Subrc::as_ptr(&items).addr()
+        );
+
+        let whole = Subrc::whole(rc.clone());
+        assert_eq!(Subrc::projection_offset(&whole), Some(0));
+        let indirect = Subrc::new_indirect(Rc::new(vec![1u8]), |v| &v[0]);
+        assert_eq!(Subrc::projection_offset(&indirect), None);
+    }
+
+    #[test]
+    fn test_cloned() {
+        let rc = Rc::new(foo());
+        let name = Subrc::new(rc.clone(), |foo| &foo.name);
+        let cloned: [u8; 5] = Subrc::cloned(&name);
+        assert_eq!(&cloned, b"hello");
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+
+    #[test]
+    fn test_to_rc_and_to_arc() {
+        let rc = Rc::new(foo());
+        let name = Rc::downgrade(&rc);
+        let items = Subrc::to_rc(&Subrc::new(rc, |foo| &foo.items));
+        assert_eq!(*items, [1, 2, 3, 4]);
+        assert!(name.upgrade().is_none());
+
+        let arc = Arc::new(foo());
+        let name = Subarc::to_arc(&Subarc::new(arc.clone(), |foo| &foo.name));
+        assert_eq!(&*name, b"hello");
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+
+    #[test]
+    fn test_try_unwrap_owner() {
+        let rc = Rc::new(foo());
+        let items = Subrc::new(rc.clone(), |foo| &foo.items);
+        let items = match Subrc::try_unwrap_owner(items) {
+            Ok(_) => panic!("the owned value is shared"),
+            Err(items) => items,
+        };
+        assert_eq!(*items, [1, 2, 3, 4]);
+
+        drop(rc);
+        let foo = Subrc::try_unwrap_owner(items).ok().unwrap();
+        assert_eq!(foo.name, *b"hello");
+    }
+
+    #[test]
+    fn test_leak() {
+        let rc = Rc::new(foo());
+        let weak = Rc::downgrade(&rc);
+        let items: &'static [u16] = Subrc::leak(Subrc::new(rc, |foo| &foo.items[..2]));
+        assert_eq!(items, &[1, 2]);
+        assert_eq!(weak.strong_count(), 1);
+    }
+
+    #[test]
+    fn test_subrc_str() {
+        let rc = Rc::new(foo());
+        let subrc: Subrc<Foo, str> = Subrc::new(rc.clone(), |foo| {
+            std::str::from_utf8(&foo.name[1..4]).unwrap()
+        });
+        assert_eq!(&*subrc, "ell");
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{
+        Arc, LockResult, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+        TryLockError, TryLockResult,
+    },
+};
+
+fn map_lock_result<G, H>(result: LockResult<G>, f: impl FnOnce(G) -> H) -> LockResult<H> {
+    match result {
+        Ok(guard) => Ok(f(guard)),
+        Err(e) => Err(PoisonError::new(f(e.into_inner()))),
+    }
+}
+
+fn map_try_lock_result<G, H>(result: TryLockResult<G>, f: impl FnOnce(G) -> H) -> TryLockResult<H> {
+    match result {
+        Ok(guard) => Ok(f(guard)),
+        Err(TryLockError::Poisoned(e)) => {
+            Err(TryLockError::Poisoned(PoisonError::new(f(e.into_inner()))))
+        }
+        Err(TryLockError::WouldBlock) => Err(TryLockError::WouldBlock),
+    }
+}
+
+/**
+* A projection to a subregion of the value behind an `Arc<Mutex<T>>`.
+*
+* Locking it locks the whole [`Mutex`], but the returned guard only gives access to the projected
+* subregion, so it can be shared with code which should only see that part of the value.
+*
+* # Example
+```rust
+# use std::sync::{Arc, Mutex};
+# use subrc::SubMutex;
+struct State {
+    name: String,
+    count: u32,
+}
+
+let arc = Arc::new(Mutex::new(State { name: String::from("state"), count: 0 }));
+let count = SubMutex::new(arc.clone(), |state| &mut state.count);
+std::thread::spawn(move || *count.lock().unwrap() += 1)
+    .join()
+    .unwrap();
+assert_eq!(arc.lock().unwrap().count, 1);
+```
+*/
+pub struct SubMutex<T: ?Sized, U: ?Sized> {
+    owner: Arc<Mutex<T>>,
+    getter: fn(&mut T) -> &mut U,
+}
+
+impl<T, U> SubMutex<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /**
+       Create a [`SubMutex`] projecting the value behind `owner` to the subregion returned by
+       `getter`, which is called every time the mutex is locked.
+    */
+    pub fn new(owner: Arc<Mutex<T>>, getter: fn(&mut T) -> &mut U) -> Self {
+        SubMutex { owner, getter }
+    }
+
+    fn project<'a>(&'a self, mut guard: MutexGuard<'a, T>) -> SubMutexGuard<'a, T, U> {
+        let ptr = (self.getter)(&mut guard) as *mut U;
+        SubMutexGuard { ptr, _guard: guard }
+    }
+
+    /**
+       Lock the mutex, returning a guard to the projected subregion, see [`Mutex::lock`].
+
+       # Errors
+       Like [`Mutex::lock`], returns an error containing the guard if the mutex is poisoned.
+    */
+    pub fn lock(&self) -> LockResult<SubMutexGuard<'_, T, U>> {
+        map_lock_result(self.owner.lock(), |guard| self.project(guard))
+    }
+
+    /**
+       Attempt to lock the mutex without blocking, see [`Mutex::try_lock`].
+    */
+    pub fn try_lock(&self) -> TryLockResult<SubMutexGuard<'_, T, U>> {
+        map_try_lock_result(self.owner.try_lock(), |guard| self.project(guard))
+    }
+}
+
+impl<T, U> Clone for SubMutex<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    fn clone(&self) -> Self {
+        SubMutex {
+            owner: self.owner.clone(),
+            getter: self.getter,
+        }
+    }
+}
+
+/**
+ A guard giving access to the projected subregion while the [`Mutex`] of a [`SubMutex`] is
+ locked, like a mapped [`MutexGuard`].
+*/
+pub struct SubMutexGuard<'a, T: ?Sized, U: ?Sized> {
+    ptr: *mut U,
+    _guard: MutexGuard<'a, T>,
+}
+
+// SAFETY: like `MutexGuard`, sharing the guard only gives shared access to the target.
+unsafe impl<T: ?Sized, U: ?Sized + Sync> Sync for SubMutexGuard<'_, T, U> {}
+
+impl<T, U> Deref for SubMutexGuard<'_, T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: the target was borrowed from the value the guard keeps locked.
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T, U> DerefMut for SubMutexGuard<'_, T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut U {
+        // SAFETY: see `deref`.
+        unsafe { &mut *self.ptr }
+    }
+}
+
+/**
+* A projection to a subregion of the value behind an `Arc<RwLock<T>>`.
+*
+* Like [`SubMutex`], it locks the whole [`RwLock`], but the returned guards only give access to
+* the projected subregion. Reading and writing need a getter each.
+*
+* # Example
+```rust
+# use std::sync::{Arc, RwLock};
+# use subrc::SubRwLock;
+struct State {
+    name: String,
+    count: u32,
+}
+
+let arc = Arc::new(RwLock::new(State { name: String::from("state"), count: 0 }));
+let name = SubRwLock::new(arc.clone(), |state| &state.name, |state| &mut state.name);
+name.write().unwrap().push('!');
+assert_eq!(*name.read().unwrap(), "state!");
+```
+*/
+pub struct SubRwLock<T: ?Sized, U: ?Sized> {
+    owner: Arc<RwLock<T>>,
+    getter: fn(&T) -> &U,
+    getter_mut: fn(&mut T) -> &mut U,
+}
+
+impl<T, U> SubRwLock<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /**
+       Create a [`SubRwLock`] projecting the value behind `owner` to the subregion returned by
+       `getter` when reading, and by `getter_mut` when writing. They should return the same
+       subregion.
+    */
+    pub fn new(
+        owner: Arc<RwLock<T>>,
+        getter: fn(&T) -> &U,
+        getter_mut: fn(&mut T) -> &mut U,
+    ) -> Self {
+        SubRwLock {
+            owner,
+            getter,
+            getter_mut,
+        }
+    }
+
+    fn project<'a>(&'a self, guard: RwLockReadGuard<'a, T>) -> SubRwLockReadGuard<'a, T, U> {
+        let ptr = (self.getter)(&guard) as *const U;
+        SubRwLockReadGuard { ptr, _guard: guard }
+    }
+
+    fn project_mut<'a>(
+        &'a self,
+        mut guard: RwLockWriteGuard<'a, T>,
+    ) -> SubRwLockWriteGuard<'a, T, U> {
+        let ptr = (self.getter_mut)(&mut guard) as *mut U;
+        SubRwLockWriteGuard { ptr, _guard: guard }
+    }
+
+    /**
+       Lock the value for reading, returning a guard to the projected subregion, see
+       [`RwLock::read`].
+
+       # Errors
+       Like [`RwLock::read`], returns an error containing the guard if the lock is poisoned.
+    */
+    pub fn read(&self) -> LockResult<SubRwLockReadGuard<'_, T, U>> {
+        map_lock_result(self.owner.read(), |guard| self.project(guard))
+    }
+
+    /// Attempt to lock the value for reading without blocking, see [`RwLock::try_read`].
+    pub fn try_read(&self) -> TryLockResult<SubRwLockReadGuard<'_, T, U>> {
+        map_try_lock_result(self.owner.try_read(), |guard| self.project(guard))
+    }
+
+    /**
+       Lock the value for writing, returning a guard to the projected subregion, see
+       [`RwLock::write`].
+
+       # Errors
+       Like [`RwLock::write`], returns an error containing the guard if the lock is poisoned.
+    */
+    pub fn write(&self) -> LockResult<SubRwLockWriteGuard<'_, T, U>> {
+        map_lock_result(self.owner.write(), |guard| self.project_mut(guard))
+    }
+
+    /// Attempt to lock the value for writing without blocking, see [`RwLock::try_write`].
+    pub fn try_write(&self) -> TryLockResult<SubRwLockWriteGuard<'_, T, U>> {
+        map_try_lock_result(self.owner.try_write(), |guard| self.project_mut(guard))
+    }
+}
+
+impl<T, U> Clone for SubRwLock<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    fn clone(&self) -> Self {
+        SubRwLock {
+            owner: self.owner.clone(),
+            getter: self.getter,
+            getter_mut: self.getter_mut,
+        }
+    }
+}
+
+/**
+ A guard giving shared access to the projected subregion while the [`RwLock`] of a
+ [`SubRwLock`] is locked for reading, like a mapped [`RwLockReadGuard`].
+*/
+pub struct SubRwLockReadGuard<'a, T: ?Sized, U: ?Sized> {
+    ptr: *const U,
+    _guard: RwLockReadGuard<'a, T>,
+}
+
+// SAFETY: like `RwLockReadGuard`, sharing the guard only gives shared access to the target.
+unsafe impl<T: ?Sized, U: ?Sized + Sync> Sync for SubRwLockReadGuard<'_, T, U> {}
+
+impl<T, U> Deref for SubRwLockReadGuard<'_, T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: the target was borrowed from the value the guard keeps locked.
+        unsafe { &*self.ptr }
+    }
+}
+
+/**
+ A guard giving exclusive access to the projected subregion while the [`RwLock`] of a
+ [`SubRwLock`] is locked for writing, like a mapped [`RwLockWriteGuard`].
+*/
+pub struct SubRwLockWriteGuard<'a, T: ?Sized, U: ?Sized> {
+    ptr: *mut U,
+    _guard: RwLockWriteGuard<'a, T>,
+}
+
+// SAFETY: like `RwLockWriteGuard`, sharing the guard only gives shared access to the target.
+unsafe impl<T: ?Sized, U: ?Sized + Sync> Sync for SubRwLockWriteGuard<'_, T, U> {}
+
+impl<T, U> Deref for SubRwLockWriteGuard<'_, T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // SAFETY: the target was borrowed from the value the guard keeps locked.
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T, U> DerefMut for SubRwLockWriteGuard<'_, T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut U {
+        // SAFETY: see `deref`.
+        unsafe { &mut *self.ptr }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex, RwLock, TryLockError};
+
+    use crate::{SubMutex, SubRwLock};
+
+    struct State {
+        items: Vec<u32>,
+        name: String,
+    }
+
+    fn state() -> State {
+        State {
+            items: vec![1, 2, 3],
+            name: String::from("state"),
+        }
+    }
+
+    #[test]
+    fn test_sub_mutex() {
+        let arc = Arc::new(Mutex::new(state()));
+        let items = SubMutex::new(arc.clone(), |state| &mut state.items);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let items = items.clone();
+                std::thread::spawn(move || items.lock().unwrap().push(4))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(arc.lock().unwrap().items.len(), 7);
+
+        let guard = items.lock().unwrap();
+        assert!(matches!(arc.try_lock(), Err(TryLockError::WouldBlock)));
+        assert!(matches!(items.try_lock(), Err(TryLockError::WouldBlock)));
+        drop(guard);
+    }
+
+    #[test]
+    fn test_sub_mutex_poisoned() {
+        let arc = Arc::new(Mutex::new(state()));
+        let name = SubMutex::new(arc.clone(), |state| &mut state.name);
+        let cloned = name.clone();
+        std::thread::spawn(move || {
+            let _guard = cloned.lock().unwrap();
+            panic!("poison the mutex");
+        })
+        .join()
+        .unwrap_err();
+
+        let guard = name.lock().err().unwrap().into_inner();
+        assert_eq!(*guard, "state");
+    }
+
+    #[test]
+    fn test_sub_rw_lock() {
+        let arc = Arc::new(RwLock::new(state()));
+        let items = SubRwLock::new(arc.clone(), |state| &state.items, |state| &mut state.items);
+        items.write().unwrap()[0] = 42;
+
+        let (a, b) = (items.read().unwrap(), items.try_read().unwrap());
+        assert_eq!(*a, [42, 2, 3]);
+        assert_eq!(*b, [42, 2, 3]);
+        assert!(matches!(items.try_write(), Err(TryLockError::WouldBlock)));
+    }
+}
@@ -0,0 +1,225 @@
+/*!
+ Projections to subregions of values behind [`tokio::sync`](::tokio::sync) locks.
+
+ These are the asynchronous counterparts of [`SubMutex`](crate::SubMutex) and
+ [`SubRwLock`](crate::SubRwLock), handing out the mapped guards of [`tokio`](::tokio).
+*/
+
+use std::sync::Arc;
+
+use ::tokio::sync::{
+    MappedMutexGuard, Mutex, MutexGuard, RwLock, RwLockMappedWriteGuard, RwLockReadGuard,
+    RwLockWriteGuard, TryLockError,
+};
+
+/**
+* A projection to a subregion of the value behind an `Arc<tokio::sync::Mutex<T>>`.
+*
+* # Example
+```rust
+# use std::sync::Arc;
+# use tokio::sync::Mutex;
+# use subrc::tokio::SubMutex;
+struct State {
+    name: String,
+    count: u32,
+}
+
+# #[tokio::main(flavor = "current_thread")]
+# async fn main() {
+let arc = Arc::new(Mutex::new(State { name: String::from("state"), count: 0 }));
+let count = SubMutex::new(arc.clone(), |state| &mut state.count);
+tokio::spawn(async move { *count.lock().await += 1 }).await.unwrap();
+assert_eq!(arc.lock().await.count, 1);
+# }
+```
+*/
+pub struct SubMutex<T: ?Sized, U: ?Sized> {
+    owner: Arc<Mutex<T>>,
+    getter: fn(&mut T) -> &mut U,
+}
+
+impl<T, U> SubMutex<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /**
+       Create a [`SubMutex`] projecting the value behind `owner` to the subregion returned by
+       `getter`, which is called every time the mutex is locked.
+    */
+    pub fn new(owner: Arc<Mutex<T>>, getter: fn(&mut T) -> &mut U) -> Self {
+        SubMutex { owner, getter }
+    }
+
+    /// Lock the mutex, returning a guard to the projected subregion, see [`Mutex::lock`].
+    pub async fn lock(&self) -> MappedMutexGuard<'_, U> {
+        MutexGuard::map(self.owner.lock().await, self.getter)
+    }
+
+    /// Attempt to lock the mutex without waiting, see [`Mutex::try_lock`].
+    pub fn try_lock(&self) -> Result<MappedMutexGuard<'_, U>, TryLockError> {
+        Ok(MutexGuard::map(self.owner.try_lock()?, self.getter))
+    }
+}
+
+impl<T, U> Clone for SubMutex<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    fn clone(&self) -> Self {
+        SubMutex {
+            owner: self.owner.clone(),
+            getter: self.getter,
+        }
+    }
+}
+
+/**
+* A projection to a subregion of the value behind an `Arc<tokio::sync::RwLock<T>>`.
+*
+* # Example
+```rust
+# use std::sync::Arc;
+# use tokio::sync::RwLock;
+# use subrc::tokio::SubRwLock;
+struct State {
+    name: String,
+    count: u32,
+}
+
+# #[tokio::main(flavor = "current_thread")]
+# async fn main() {
+let arc = Arc::new(RwLock::new(State { name: String::from("state"), count: 0 }));
+let name = SubRwLock::new(arc.clone(), |state| &state.name, |state| &mut state.name);
+name.write().await.push('!');
+assert_eq!(*name.read().await, "state!");
+# }
+```
+*/
+pub struct SubRwLock<T: ?Sized, U: ?Sized> {
+    owner: Arc<RwLock<T>>,
+    getter: fn(&T) -> &U,
+    getter_mut: fn(&mut T) -> &mut U,
+}
+
+impl<T, U> SubRwLock<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    /**
+       Create a [`SubRwLock`] projecting the value behind `owner` to the subregion returned by
+       `getter` when reading, and by `getter_mut` when writing. They should return the same
+       subregion.
+    */
+    pub fn new(
+        owner: Arc<RwLock<T>>,
+        getter: fn(&T) -> &U,
+        getter_mut: fn(&mut T) -> &mut U,
+    ) -> Self {
+        SubRwLock {
+            owner,
+            getter,
+            getter_mut,
+        }
+    }
+
+    /**
+       Lock the value for reading, returning a guard to the projected subregion, see
+       [`RwLock::read`].
+    */
+    pub async fn read(&self) -> RwLockReadGuard<'_, U> {
+        RwLockReadGuard::map(self.owner.read().await, self.getter)
+    }
+
+    /// Attempt to lock the value for reading without waiting, see [`RwLock::try_read`].
+    pub fn try_read(&self) -> Result<RwLockReadGuard<'_, U>, TryLockError> {
+        Ok(RwLockReadGuard::map(self.owner.try_read()?, self.getter))
+    }
+
+    /**
+       Lock the value for writing, returning a guard to the projected subregion, see
+       [`RwLock::write`].
+    */
+    pub async fn write(&self) -> RwLockMappedWriteGuard<'_, U> {
+        RwLockWriteGuard::map(self.owner.write().await, self.getter_mut)
+    }
+
+    /// Attempt to lock the value for writing without waiting, see [`RwLock::try_write`].
+    pub fn try_write(&self) -> Result<RwLockMappedWriteGuard<'_, U>, TryLockError> {
+        Ok(RwLockWriteGuard::map(
+            self.owner.try_write()?,
+            self.getter_mut,
+        ))
+    }
+}
+
+impl<T, U> Clone for SubRwLock<T, U>
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    fn clone(&self) -> Self {
+        SubRwLock {
+            owner: self.owner.clone(),
+            getter: self.getter,
+            getter_mut: self.getter_mut,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ::tokio::sync::{Mutex, RwLock};
+
+    use super::{SubMutex, SubRwLock};
+
+    struct State {
+        items: Vec<u32>,
+        name: String,
+    }
+
+    fn state() -> State {
+        State {
+            items: vec![1, 2, 3],
+            name: String::from("state"),
+        }
+    }
+
+    #[::tokio::test]
+    async fn test_sub_mutex() {
+        let arc = Arc::new(Mutex::new(state()));
+        let items = SubMutex::new(arc.clone(), |state| &mut state.items);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let items = items.clone();
+                ::tokio::spawn(async move { items.lock().await.push(4) })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(arc.lock().await.items.len(), 7);
+
+        let guard = items.lock().await;
+        assert!(arc.try_lock().is_err());
+        assert!(items.try_lock().is_err());
+        drop(guard);
+    }
+
+    #[::tokio::test]
+    async fn test_sub_rw_lock() {
+        let arc = Arc::new(RwLock::new(state()));
+        let name = SubRwLock::new(arc.clone(), |state| &state.name, |state| &mut state.name);
+        name.write().await.push('!');
+
+        let (a, b) = (name.read().await, name.try_read().unwrap());
+        assert_eq!(*a, "state!");
+        assert_eq!(*b, "state!");
+        assert!(name.try_write().is_err());
+    }
+}
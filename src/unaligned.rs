@@ -0,0 +1,208 @@
+use core::{fmt, marker::PhantomData, mem, ptr::NonNull};
+
+#[cfg(doc)]
+use crate::subrc_unaligned;
+use crate::{
+    subptr::{check_offset, invalid_projection},
+    ProjectionError, SharedOwner, Subptr,
+};
+
+/**
+* A projection to a possibly misaligned `U`, such as a field of a `#[repr(packed)]` struct, which
+* hands out copies of the target instead of references.
+*
+* A [`Subptr`] always derefs to a reference, which must be aligned, so [`Subptr::new`] rejects
+* misaligned targets with [`ProjectionError::Misaligned`], and the compiler rejects references to
+* packed fields outright. This projection is instead created from a raw pointer to the field, and
+* reads it with [`read_unaligned`](core::ptr::read_unaligned). The [`subrc_unaligned`] macro
+* creates one safely from the path of the field.
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{subrc_unaligned, SubrcUnaligned};
+#[repr(C, packed)]
+struct Header {
+    kind: u8,
+    length: u32,
+}
+
+let rc = Rc::new(Header { kind: 1, length: 512 });
+let length: SubrcUnaligned<Header, u32> = subrc_unaligned!(rc, Header, length);
+assert_eq!(length.get(), 512);
+```
+*/
+pub struct SubptrUnaligned<P, U: Copy> {
+    owner: P,
+    ptr: NonNull<U>,
+    // Invariant in `U` like `Subptr`, which it converts into.
+    _target: PhantomData<fn(U) -> U>,
+}
+
+// SAFETY: like a `Subptr`, it behaves like its owner bundled with shared access to the target.
+unsafe impl<P: Send, U: Copy + Sync> Send for SubptrUnaligned<P, U> {}
+unsafe impl<P: Sync, U: Copy + Sync> Sync for SubptrUnaligned<P, U> {}
+
+impl<P, U> SubptrUnaligned<P, U>
+where
+    P: SharedOwner,
+    U: Copy,
+{
+    /**
+       Create a [`SubptrUnaligned`] to the possibly misaligned subregion of the value owned by
+       `owner` returned by `getter`, e.g. `|t| &raw const t.field`. See [`subrc_unaligned`] for
+       a safe alternative.
+
+       # Safety
+       Only the bounds of the pointer are checked, so if it lies within the owned value, it must
+       point to a valid, possibly misaligned `U`, such as a field of type `U`. A pointer cast to
+       another type could read padding or invalid values.
+
+       # Panics
+       Like [`Subptr::new`], `getter` must return a pointer to a subregion of the owned value. Use
+       [`try_new`](SubptrUnaligned::try_new) to handle this case gracefully.
+    */
+    #[track_caller]
+    pub unsafe fn new<F>(owner: P, getter: F) -> Self
+    where
+        F: FnOnce(&P::Target) -> *const U,
+    {
+        let ptr = getter(&owner);
+        if let Err(e) = check_unaligned(&*owner, ptr) {
+            invalid_projection::<P::Target, U>(e, &*owner, (ptr.addr(), mem::size_of::<U>()));
+        }
+        // SAFETY: the pointer lies within the owned value, so it isn't null.
+        let ptr = unsafe { NonNull::new_unchecked(ptr as *mut U) };
+        SubptrUnaligned {
+            owner,
+            ptr,
+            _target: PhantomData,
+        }
+    }
+
+    /**
+       Like [`new`](SubptrUnaligned::new), but returns a [`ProjectionError`] instead of panicking
+       if `getter` does not return a pointer to a subregion of the owned value.
+
+       # Safety
+       See [`new`](SubptrUnaligned::new).
+    */
+    pub unsafe fn try_new<F>(owner: P, getter: F) -> Result<Self, ProjectionError>
+    where
+        F: FnOnce(&P::Target) -> *const U,
+    {
+        let ptr = getter(&owner);
+        check_unaligned(&*owner, ptr)?;
+        Ok(SubptrUnaligned {
+            owner,
+            // SAFETY: the pointer lies within the owned value, so it isn't null.
+            ptr: unsafe { NonNull::new_unchecked(ptr as *mut U) },
+            _target: PhantomData,
+        })
+    }
+
+    /// Get the owner of the projected value.
+    pub fn owner(&self) -> &P {
+        &self.owner
+    }
+
+    /// Read a copy of the projected value.
+    pub fn get(&self) -> U {
+        // SAFETY: the target lies within the owned value, which the owner keeps alive and shared.
+        unsafe { self.ptr.as_ptr().read_unaligned() }
+    }
+
+    /// Return a plain [`Subptr`] to the target if it happens to be aligned, or the projection back
+    /// otherwise.
+    pub fn try_into_aligned(self) -> Result<Subptr<P, U>, Self> {
+        if !self.ptr.is_aligned() {
+            return Err(self);
+        }
+        // SAFETY: the target is aligned, and lies within the owned value.
+        Ok(unsafe { Subptr::from_raw_parts(self.owner, self.ptr.as_ptr()) })
+    }
+}
+
+/// Check that the possibly misaligned `u` lies within `t`.
+fn check_unaligned<T: ?Sized, U>(t: &T, u: *const U) -> Result<usize, ProjectionError> {
+    check_offset(
+        ((t as *const T).addr(), mem::size_of_val(t)),
+        (u.addr(), mem::size_of::<U>()),
+        1,
+    )
+}
+
+impl<P, U> Clone for SubptrUnaligned<P, U>
+where
+    P: SharedOwner,
+    U: Copy,
+{
+    fn clone(&self) -> Self {
+        SubptrUnaligned {
+            owner: self.owner.clone(),
+            ptr: self.ptr,
+            _target: PhantomData,
+        }
+    }
+}
+
+impl<P, U> fmt::Debug for SubptrUnaligned<P, U>
+where
+    P: SharedOwner,
+    U: Copy + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use crate::{subrc_unaligned, ProjectionError, SubarcUnaligned, SubrcUnaligned};
+
+    #[repr(C, packed)]
+    struct Header {
+        kind: u8,
+        length: u32,
+        checksum: u16,
+    }
+
+    fn header() -> Header {
+        Header {
+            kind: 1,
+            length: 512,
+            checksum: 0xbeef,
+        }
+    }
+
+    #[test]
+    fn test_unaligned_fields() {
+        let rc = Rc::new(header());
+        let length: SubrcUnaligned<_, u32> = subrc_unaligned!(rc, Header, length);
+        let checksum = subrc_unaligned!(rc, Header, checksum);
+        assert_eq!(length.get(), 512);
+        assert_eq!(checksum.clone().get(), 0xbeef);
+        assert_eq!(format!("{:?}", length), "512");
+
+        assert!(length.try_into_aligned().is_err());
+        // SAFETY: the pointer is to a `u8` field.
+        let kind = unsafe { SubrcUnaligned::new(rc, |h| &raw const h.kind) };
+        assert_eq!(*kind.try_into_aligned().ok().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_unaligned_out_of_bounds() {
+        let arc = Arc::new(header());
+        let other = 0u16;
+        // SAFETY: neither pointer lies within the owned value, so neither is read.
+        unsafe {
+            assert!(SubarcUnaligned::try_new(arc.clone(), |_| &raw const other).is_err());
+            assert_eq!(
+                SubarcUnaligned::try_new(arc, |h| (&raw const h.checksum).cast::<u32>()).err(),
+                Some(ProjectionError::OutOfBounds)
+            );
+        }
+    }
+}
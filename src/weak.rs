@@ -0,0 +1,245 @@
+use alloc::{rc, sync};
+use core::{marker::PhantomData, ops::Deref, ptr::NonNull};
+
+use crate::{Pointee, Subptr, WeakOwner};
+
+/**
+* A weak version of [`Subptr`], which points to a sub-region of the owned value without keeping it
+* alive.
+*
+* It is created by [`Subptr::downgrade`], and can be turned back into a [`Subptr`] with
+* [`upgrade`](WeakSubptr::upgrade) as long as the owner is still alive.
+*
+* Like a [`Subptr`], it is invariant in `U`, so its target can't be shortened either:
+```compile_fail
+# use std::rc::Rc;
+# use subrc::WeakSubptr;
+type Owner = Rc<(&'static str, u8)>;
+
+fn shorten<'a>(weak: WeakSubptr<Owner, &'static str>) -> WeakSubptr<Owner, &'a str> {
+    weak
+}
+```
+*
+* # Example
+```rust
+# use std::rc::Rc;
+# use subrc::{subrc, Subrc};
+struct Foo {
+    value: i32,
+}
+
+let rc = Rc::new(Foo { value: 42 });
+let weak = Subrc::downgrade(&subrc!(rc.value));
+assert_eq!(*weak.upgrade().unwrap(), 42);
+
+drop(rc);
+assert!(weak.upgrade().is_none());
+```
+*/
+pub struct WeakSubptr<P: WeakOwner, U: ?Sized + Pointee> {
+    owner: P::Weak,
+    ptr: NonNull<U>,
+    // Invariant in `U` like `Subptr`, which it upgrades to.
+    _invariant: PhantomData<fn(U) -> U>,
+}
+
+// SAFETY: the pointer is only dereferenced through an upgraded `Subptr`, which has the same bounds.
+unsafe impl<P, U> Send for WeakSubptr<P, U>
+where
+    P: WeakOwner,
+    P::Weak: Send,
+    U: ?Sized + Pointee + Sync,
+{
+}
+unsafe impl<P, U> Sync for WeakSubptr<P, U>
+where
+    P: WeakOwner,
+    P::Weak: Sync,
+    U: ?Sized + Pointee + Sync,
+{
+}
+
+#[cfg(feature = "nightly")]
+impl<P, U, V> core::ops::CoerceUnsized<WeakSubptr<P, V>> for WeakSubptr<P, U>
+where
+    P: WeakOwner,
+    U: ?Sized + Pointee + core::marker::Unsize<V>,
+    V: ?Sized + Pointee,
+{
+}
+
+impl<P, U> Clone for WeakSubptr<P, U>
+where
+    P: WeakOwner,
+    U: ?Sized + Pointee,
+{
+    fn clone(&self) -> Self {
+        WeakSubptr {
+            owner: self.owner.clone(),
+            ptr: self.ptr,
+            _invariant: PhantomData,
+        }
+    }
+}
+
+impl<P, U> WeakSubptr<P, U>
+where
+    P: WeakOwner,
+    U: ?Sized + Pointee,
+{
+    /**
+       Attempt to upgrade the weak pointer to a [`Subptr`], which keeps the owner alive.
+
+       Returns [`None`] if the owned value has already been dropped.
+    */
+    pub fn upgrade(&self) -> Option<Subptr<P, U>> {
+        let owner = P::upgrade(&self.owner)?;
+        // SAFETY: the pointer was valid for this very owner when the `Subptr` we were downgraded
+        // from was created, and the owner is still alive.
+        Some(unsafe { Subptr::from_raw_parts(owner, self.ptr.as_ptr()) })
+    }
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: WeakOwner,
+    U: ?Sized + Pointee,
+{
+    /**
+       Create a [`WeakSubptr`] pointing to the same subregion, without keeping the owner alive.
+    */
+    pub fn downgrade(this: &Self) -> WeakSubptr<P, U> {
+        WeakSubptr {
+            owner: P::downgrade(this.owner()),
+            ptr: NonNull::from(this.get()),
+            _invariant: PhantomData,
+        }
+    }
+}
+
+/// Implementation detail of the [`subweak_field`](crate::subweak_field) macro: a weak pointer
+/// whose target address is known even before the value is initialized.
+#[doc(hidden)]
+pub trait WeakPtr: Clone {
+    type Owner: WeakOwner<Weak = Self>;
+
+    fn as_ptr(&self) -> *const <Self::Owner as Deref>::Target;
+}
+
+impl<T> WeakPtr for rc::Weak<T> {
+    type Owner = rc::Rc<T>;
+
+    fn as_ptr(&self) -> *const T {
+        rc::Weak::as_ptr(self)
+    }
+}
+
+impl<T> WeakPtr for sync::Weak<T> {
+    type Owner = sync::Arc<T>;
+
+    fn as_ptr(&self) -> *const T {
+        sync::Weak::as_ptr(self)
+    }
+}
+
+/**
+ Implementation detail of the [`subweak_field`](crate::subweak_field) macro.
+
+ # Safety
+ `offset` must be the offset of the field `_getter` projects to, within the value `weak` points
+ to. `_getter` is never called, it only pins down the owner and field types.
+
+ The macro computes `offset` like [`subrc_field`](crate::subrc_field), see
+ `Subptr::from_field_offset`.
+*/
+#[doc(hidden)]
+pub unsafe fn weak_from_field_offset<W, U, F>(
+    weak: &W,
+    offset: usize,
+    _getter: F,
+) -> WeakSubptr<W::Owner, U>
+where
+    W: WeakPtr,
+    U: Pointee,
+    F: FnOnce(&<W::Owner as Deref>::Target) -> &U,
+{
+    let ptr = weak.as_ptr().cast::<u8>().wrapping_add(offset).cast::<U>();
+    WeakSubptr {
+        owner: weak.clone(),
+        // A weak pointer which doesn't point to any allocation can't be upgraded, so neither can
+        // the projection, and its pointer is never used.
+        ptr: NonNull::new(ptr as *mut U).unwrap_or(NonNull::dangling()),
+        _invariant: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use crate::{subweak_field, SubWeak, Subarc, SubarcWeak, Subrc};
+
+    struct Foo {
+        _value: i32,
+        bar: (u8, u16),
+    }
+
+    #[test]
+    fn test_upgrade_while_alive() {
+        let rc = Rc::new(Foo {
+            _value: 42,
+            bar: (1, 2),
+        });
+        let weak = Subrc::downgrade(&Subrc::new(rc.clone(), |foo| &foo.bar.1));
+
+        let subrc = weak.upgrade().unwrap();
+        assert!(std::ptr::eq(&*subrc, &rc.bar.1));
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+
+    #[test]
+    fn test_upgrade_after_drop() {
+        let arc = Arc::new(Foo {
+            _value: 42,
+            bar: (1, 2),
+        });
+        let weak = Subarc::downgrade(&Subarc::new(arc.clone(), |foo| &foo.bar));
+        assert_eq!(Arc::strong_count(&arc), 1);
+
+        drop(arc);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_subweak_field_in_new_cyclic() {
+        struct Node {
+            name: String,
+            this: SubWeak<Node, String>,
+        }
+
+        let node = Rc::new_cyclic(|weak| Node {
+            name: String::from("root"),
+            this: subweak_field!(weak, Node, name),
+        });
+        let name = node.this.upgrade().unwrap();
+        assert!(std::ptr::eq(&*name, &node.name));
+        assert_eq!(Rc::strong_count(&node), 2);
+    }
+
+    #[test]
+    fn test_subweak_field_of_arc() {
+        let arc = Arc::new(Foo {
+            _value: 42,
+            bar: (1, 2),
+        });
+        let weak = Arc::downgrade(&arc);
+        let bar: SubarcWeak<Foo, u16> = subweak_field!(&weak, Foo, bar.1);
+        assert!(std::ptr::eq(&*bar.upgrade().unwrap(), &arc.bar.1));
+
+        drop(arc);
+        assert!(bar.upgrade().is_none());
+        let dangling: SubWeak<Foo, u16> = subweak_field!(&std::rc::Weak::new(), Foo, bar.1);
+        assert!(dangling.upgrade().is_none());
+    }
+}
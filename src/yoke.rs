@@ -0,0 +1,94 @@
+use ::yoke::{Yoke, Yokeable};
+use stable_deref_trait::StableDeref;
+
+use crate::{Pointee, SharedOwner, Subptr};
+
+/// Turn the projection into a [`Yoke`] borrowing its target from its owner, which becomes the
+/// cart.
+impl<P, U> From<Subptr<P, U>> for Yoke<&'static U, P>
+where
+    P: SharedOwner + StableDeref,
+    P::Target: 'static,
+    U: ?Sized + Pointee + 'static,
+{
+    fn from(subptr: Subptr<P, U>) -> Self {
+        let (owner, ptr) = Subptr::into_raw_parts(subptr);
+        // SAFETY: the target is kept alive and in place by the owner, which the yoke keeps along
+        // with the reference.
+        Yoke::attach_to_cart(owner, |_| unsafe { &*ptr })
+    }
+}
+
+/// Turn a [`Yoke`] borrowing from its cart back into a projection owned by the cart.
+impl<P, U> From<Yoke<&'static U, P>> for Subptr<P, U>
+where
+    P: SharedOwner + StableDeref,
+    U: ?Sized + Pointee + 'static,
+{
+    fn from(yoke: Yoke<&'static U, P>) -> Self {
+        let ptr: *const U = *yoke.get();
+        // SAFETY: the reference either borrows from the value behind the cart, which stays in
+        // place for as long as the cart exists, or is truly `'static`.
+        unsafe { Subptr::from_raw_parts(yoke.into_backing_cart(), ptr) }
+    }
+}
+
+impl<P, U> Subptr<P, U>
+where
+    P: SharedOwner,
+    U: ?Sized + Pointee + 'static,
+{
+    /**
+       Create a [`Yoke`] using this projection as its cart, with a yokeable borrowing from the
+       target, see [`Yoke::attach_to_cart`].
+
+       ## Example
+       ```rust
+           # use std::{borrow::Cow, rc::Rc};
+           # use subrc::Subrc;
+           # use yoke::Yoke;
+           let rc = Rc::new((1u8, String::from("one two")));
+           let text: Subrc<_, str> = Subrc::new_indirect(rc, |t| t.1.as_str());
+           let last: Yoke<Cow<'static, str>, _> =
+               Subrc::attach_yoke(text, |text| Cow::Borrowed(text.rsplit(' ').next().unwrap()));
+           assert_eq!(last.get(), "two");
+       ```
+    */
+    pub fn attach_yoke<Y, F>(this: Self, f: F) -> Yoke<Y, Self>
+    where
+        Y: for<'a> Yokeable<'a>,
+        F: for<'de> FnOnce(&'de U) -> <Y as Yokeable<'de>>::Output,
+    {
+        Yoke::attach_to_cart(this, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use yoke::Yoke;
+
+    use crate::Subrc;
+
+    #[test]
+    fn test_yoke_round_trip() {
+        let rc = Rc::new((1u8, [2u16, 3]));
+        let yoke: Yoke<&'static [u16; 2], Rc<_>> = Subrc::new(rc.clone(), |t| &t.1).into();
+        assert!(std::ptr::eq(*yoke.get(), &rc.1));
+        assert_eq!(Rc::strong_count(&rc), 2);
+
+        let subrc = Subrc::from(yoke);
+        assert!(std::ptr::eq(&*subrc, &rc.1));
+        assert_eq!(Rc::strong_count(&rc), 2);
+    }
+
+    #[test]
+    fn test_attach_yoke() {
+        let rc = Rc::new((1u8, [2u16, 3]));
+        let yoke: Yoke<&'static u16, _> =
+            Subrc::attach_yoke(Subrc::new(rc.clone(), |t| &t.1), |items| &items[1]);
+        assert!(std::ptr::eq(*yoke.get(), &rc.1[1]));
+        assert!(std::ptr::eq(&**yoke.backing_cart(), &rc.1));
+    }
+}
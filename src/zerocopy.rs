@@ -0,0 +1,102 @@
+use ::zerocopy::{ConvertError, FromBytes, Immutable, KnownLayout};
+use core::ops::{Bound, RangeBounds};
+
+use crate::{CastError, Pointee, SharedOwner, Subptr};
+
+impl<P, V> Subptr<P, V>
+where
+    P: SharedOwner,
+    P::Target: AsRef<[u8]>,
+    V: ?Sized + Pointee + FromBytes + KnownLayout + Immutable,
+{
+    /**
+       Create a [`Subptr`] viewing the bytes in `range` of the owned buffer as a `V`, checking
+       that they have its size and alignment, like [`project_range`](Subptr::project_range) does
+       for the raw bytes.
+
+       `V` may also be a slice `[E]`, which spans as many elements as fit in the range.
+
+       ## Example
+       ```rust
+           # use std::rc::Rc;
+           # use subrc::Subrc;
+           use zerocopy::little_endian::{U16, U32};
+
+           let rc = Rc::new(vec![0x2a, 0, 0, 0, 1, 0, 2, 0]);
+           let length: Subrc<Vec<u8>, U32> = Subrc::project_as(&rc, ..4).unwrap();
+           let items: Subrc<Vec<u8>, [U16]> = Subrc::project_as(&rc, 4..).unwrap();
+           assert_eq!(u32::from(*length), 42);
+           assert_eq!(items[1].get(), 2);
+       ```
+    */
+    pub fn project_as<R>(owner: &P, range: R) -> Result<Self, CastError>
+    where
+        R: RangeBounds<usize>,
+    {
+        let bounds: (Bound<usize>, Bound<usize>) =
+            (range.start_bound().cloned(), range.end_bound().cloned());
+        let bytes = (**owner)
+            .as_ref()
+            .get(bounds)
+            .ok_or(CastError::OutOfRange)?;
+        let v = V::ref_from_bytes(bytes).map_err(|e| match e {
+            ConvertError::Alignment(_) => CastError::Misaligned,
+            ConvertError::Size(_) => CastError::SizeMismatch,
+            ConvertError::Validity(never) => match never {},
+        })?;
+        let ptr = v as *const V;
+        // SAFETY: the value was borrowed from the owned value, which the clone of `owner` keeps
+        // alive and shared, see `new_indirect`.
+        Ok(unsafe { Subptr::from_raw_parts(owner.clone(), ptr) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use ::zerocopy::little_endian::U16;
+
+    use crate::{CastError, Subarc, Subrc};
+
+    #[test]
+    fn test_project_as() {
+        let rc: Rc<[u8]> = Rc::from(&[1u8, 0, 2, 0, 3][..]);
+        let first: Subrc<[u8], U16> = Subrc::project_as(&rc, ..2).unwrap();
+        assert_eq!(u16::from(*first), 1);
+        let items: Subrc<[u8], [U16]> = Subrc::project_as(&rc, ..4).unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(std::ptr::eq(items.as_ptr().cast::<u8>(), rc.as_ptr()));
+
+        assert_eq!(
+            Subrc::<_, U16>::project_as(&rc, 1..4).err(),
+            Some(CastError::SizeMismatch)
+        );
+        assert_eq!(
+            Subrc::<_, [U16]>::project_as(&rc, ..5).err(),
+            Some(CastError::SizeMismatch)
+        );
+        assert_eq!(
+            Subrc::<_, U16>::project_as(&rc, 4..6).err(),
+            Some(CastError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_project_as_aligned() {
+        let arc = Arc::new(vec![0u8; 16]);
+        let misaligned = (1..4)
+            .find(|start| !(arc.as_ptr().addr() + start).is_multiple_of(4))
+            .unwrap();
+        assert_eq!(
+            Subarc::<_, u32>::project_as(&arc, misaligned..misaligned + 4).err(),
+            Some(CastError::Misaligned)
+        );
+
+        let aligned = (0..4)
+            .find(|start| (arc.as_ptr().addr() + start).is_multiple_of(4))
+            .unwrap();
+        let value: Subarc<Vec<u8>, u32> = Subarc::project_as(&arc, aligned..aligned + 4).unwrap();
+        assert_eq!(*value, 0);
+    }
+}
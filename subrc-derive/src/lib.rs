@@ -0,0 +1,239 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, Visibility};
+
+/**
+ Generate a projection method for every field of a struct, returning a `Subrc` to that field.
+
+ For a field `bar: Bar` of `Foo`, this generates
+ `pub fn bar(self: &Rc<Self>) -> Subrc<Self, Bar>`. The field offsets are computed at compile
+ time, so the generated methods never panic.
+
+ # Attributes
+ - `#[project(arc)]` on the struct generates methods taking `self: &Arc<Self>` and returning a
+   `Subarc` instead.
+ - `#[project(markers = name)]` on the struct also generates a module `name` with a marker type
+   for each field, named after the field in `CamelCase`, and implements `subrc::Project` for each
+   of them, so fields can be projected to by type (see `subrc::Project`), or with the markers
+   as values, e.g. `Subrc::at(rc, name::Field)`.
+ - `#[project(skip)]` on a field skips generating a method (and a marker) for it.
+
+ # Example
+```rust
+# use std::rc::Rc;
+# use subrc::Project;
+#[derive(Project)]
+struct Foo {
+    pub bar: Bar,
+    #[project(skip)]
+    _baz: u8,
+}
+
+pub struct Bar {
+    pub value: i32,
+}
+
+let rc = Rc::new(Foo { bar: Bar { value: 42 }, _baz: 0 });
+let bar = rc.bar();
+assert_eq!(bar.value, 42);
+assert!(std::ptr::eq(&*bar, &rc.bar));
+```
+
+ Generic structs are supported as well:
+```rust
+# use std::sync::Arc;
+# use subrc::Project;
+#[derive(Project)]
+#[project(arc)]
+struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+let arc = Arc::new(Pair { first: 1u8, second: "two" });
+assert_eq!(*arc.second(), "two");
+```
+
+ With markers:
+```rust
+# use std::rc::Rc;
+# use subrc::{Project, ProjectExt};
+#[derive(Project)]
+#[project(markers = settings)]
+struct Settings {
+    user_name: String,
+    volume: u8,
+}
+
+let rc = Rc::new(Settings { user_name: String::from("ada"), volume: 7 });
+assert_eq!(*rc.project::<settings::UserName>(), "ada");
+assert_eq!(*rc.project::<settings::Volume>(), 7);
+assert_eq!(*subrc::Subrc::at(rc, settings::Volume), 7);
+```
+*/
+#[proc_macro_derive(Project, attributes(project))]
+pub fn derive_project(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let mut arc = false;
+    let mut markers = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("project"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("arc") {
+                arc = true;
+                Ok(())
+            } else if meta.path.is_ident("markers") {
+                markers = Some(meta.value()?.parse::<Ident>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `arc` or `markers`"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "`Project` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`Project` can only be derived for structs",
+            ))
+        }
+    };
+
+    let (owner, subptr) = if arc {
+        (
+            quote!(::subrc::__private::Arc<Self>),
+            quote!(::subrc::Subarc),
+        )
+    } else {
+        (quote!(::subrc::__private::Rc<Self>), quote!(::subrc::Subrc))
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut methods = Vec::new();
+    let mut marker_types = Vec::new();
+    let mut project_impls = Vec::new();
+    for field in fields {
+        let mut skip = false;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("project"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `skip`"))
+                }
+            })?;
+        }
+        if skip {
+            continue;
+        }
+
+        let name = field.ident.as_ref().expect("named fields have names");
+        let ty = &field.ty;
+        let vis = &field.vis;
+        let doc = format!("Project to the `{}` field.", name);
+        let method = format_ident!("{}", name);
+        methods.push(quote! {
+            #[doc = #doc]
+            #vis fn #method(self: &#owner) -> #subptr<Self, #ty> {
+                ::subrc::subrc_field!(*self, Self, #name)
+            }
+        });
+
+        if let Some(markers) = &markers {
+            let marker = camel_case(name);
+            let marker_vis = nested_visibility(vis);
+            let doc = format!("The marker of the `{}` field of `{}`.", name, ident);
+            marker_types.push(quote! {
+                #[doc = #doc]
+                #marker_vis struct #marker;
+            });
+            project_impls.push(quote! {
+                unsafe impl #impl_generics ::subrc::Project<#markers::#marker>
+                    for #ident #ty_generics #where_clause
+                {
+                    type Output = #ty;
+                    const OFFSET: usize = ::core::mem::offset_of!(Self, #name);
+                }
+            });
+        }
+    }
+
+    let markers = markers.map(|markers| {
+        let vis = &input.vis;
+        let doc = format!("The field markers of [`{}`], see `subrc::Project`.", ident);
+        quote! {
+            #[doc = #doc]
+            #vis mod #markers {
+                #(#marker_types)*
+            }
+
+            #(#project_impls)*
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #(#methods)*
+        }
+
+        #markers
+    })
+}
+
+/// Turn a `snake_case` field name into a `CamelCase` type name.
+fn camel_case(name: &Ident) -> Ident {
+    let camel: String = name
+        .to_string()
+        .trim_start_matches("r#")
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().expect("words are not empty");
+            first.to_uppercase().chain(chars).collect::<String>()
+        })
+        .collect();
+    format_ident!("{}", camel, span = name.span())
+}
+
+/// Get the visibility a marker in the nested markers module must have to be visible wherever its
+/// field is.
+fn nested_visibility(vis: &Visibility) -> TokenStream2 {
+    match vis {
+        Visibility::Inherited => quote!(pub(super)),
+        Visibility::Restricted(restricted) if restricted.path.is_ident("self") => {
+            quote!(pub(super))
+        }
+        Visibility::Restricted(restricted) if restricted.path.is_ident("super") => {
+            quote!(pub(in super::super))
+        }
+        _ => quote!(#vis),
+    }
+}